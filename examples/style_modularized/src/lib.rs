@@ -68,7 +68,6 @@ pub fn button_style() -> String {
             transition: color 100ms;
             vertical-align: baseline;
             user-select: none;
-            -webkit-user-select: none;
         }
         button:hover{
             background-color: yellow;
@@ -27,7 +27,6 @@ pub fn Parent() -> impl IntoView {
             transition: color 100ms;
             vertical-align: baseline;
             user-select: none;
-            -webkit-user-select: none;
         }
         button:hover{
             background-color: yellow;
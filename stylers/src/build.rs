@@ -1,18 +1,34 @@
 use camino::{Utf8Path, Utf8PathBuf};
 use color_eyre::Section;
-use color_eyre::eyre::{WrapErr as _, bail};
+use color_eyre::eyre::{Report, WrapErr as _, bail, eyre};
 use glob::glob;
 
 use std::fs::File;
 use std::io::{self, Write};
 use std::num::Saturating;
-use std::{env::current_dir, fs};
-use stylers_core::Class;
-use stylers_core::{from_str, from_ts};
-use syn::{Macro, StmtMacro};
+use std::path::Path;
+use std::{
+  env::{self, current_dir},
+  fs,
+};
+use stylers_core::{
+  CONFIG_ENV_VARS, Class, ClassNaming, Diagnostic, Indent, ScopeOptions, Targets, path_hint,
+};
+use stylers_core::{
+  from_str, from_ts, minify_css, module_from_str, module_from_ts, prefix_css, pretty_css,
+  warnings_from_str, warnings_from_ts,
+};
+use syn::Macro;
+use syn::spanned::Spanned as _;
 #[allow(unused_imports)]
 use tracing::{debug, error, info, trace, warn};
 
+mod collisions;
+mod unused;
+
+use collisions::{Emitted, EmittedClasses, SourceLocation};
+use unused::{MARKUP_MACROS, Markup};
+
 #[cfg(feature = "build-script")]
 macro_rules! p {
 ($($tokens: tt)*) => {
@@ -24,9 +40,35 @@ macro_rules! p {
   ($($tokens: tt)*) => {};
 }
 
+/// Tells cargo to rerun the build script when something changed, e.g. `rerun_if("env-changed", "STYLERS_SCOPING")`.
+#[cfg(feature = "build-script")]
+fn rerun_if(condition: &str, value: impl std::fmt::Display) {
+  println!("cargo::rerun-if-{}={}", condition, value);
+}
+#[cfg(not(feature = "build-script"))]
+fn rerun_if(_condition: &str, _value: impl std::fmt::Display) {}
+
 pub struct BuildParams {
   output_path: Utf8PathBuf,
   search_dir: Utf8PathBuf,
+  manifest_dir: Utf8PathBuf,
+  class_naming: ClassNaming,
+  scope_options: ScopeOptions,
+  targets: Targets,
+  on_collision: OnCollision,
+  minify: bool,
+  pretty: Option<Indent>,
+}
+
+/// What [`build`] does when two different css bodies end up with the same class
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum OnCollision {
+  /// Fails the build, listing the locations of every collision
+  #[default]
+  Fail,
+  /// Emits a `cargo::warning` and keeps both bodies in the output
+  Warn,
 }
 
 impl BuildParams {
@@ -45,6 +87,39 @@ pub struct BuildParamsBuilder {
   /// the stylers::style! maco
   #[cfg_attr(feature = "cli", arg(long))]
   search_dir: Option<Utf8PathBuf>,
+  /// Directory of the Cargo.toml whose `[package.metadata.stylers]`
+  /// configures the class names, defaults to the current directory
+  #[cfg_attr(feature = "cli", arg(long))]
+  manifest_dir: Option<Utf8PathBuf>,
+  #[cfg_attr(feature = "cli", arg(skip))]
+  class_naming: Option<ClassNaming>,
+  #[cfg_attr(feature = "cli", arg(skip))]
+  scope_options: Option<ScopeOptions>,
+  /// Browserslist style query of the browsers to add vendor prefixes for,
+  /// e.g. `defaults` or `safari >= 14`, defaults to the `targets` of the manifest
+  #[cfg_attr(feature = "cli", arg(long))]
+  targets: Option<Targets>,
+  /// What to do when two different css bodies end up with the same class,
+  /// defaults to failing the build
+  #[cfg_attr(feature = "cli", arg(long, value_enum))]
+  on_collision: Option<OnCollision>,
+  /// Minifies the output: removes whitespace inside of values, shortens colors
+  /// and zero lengths, merges adjacent rules and removes repeated declarations
+  #[cfg_attr(feature = "cli", arg(long))]
+  minify: bool,
+  /// Pretty prints the output for reading it, indented by the given
+  /// number of spaces or `tab`, two spaces if none is given
+  #[cfg_attr(
+    feature = "cli",
+    arg(
+      long,
+      value_name = "INDENT",
+      num_args = 0..=1,
+      default_missing_value = "2",
+      conflicts_with = "minify"
+    )
+  )]
+  pretty: Option<Indent>,
 }
 
 impl BuildParamsBuilder {
@@ -69,7 +144,7 @@ impl BuildParamsBuilder {
     }
     Ok(Self {
       output_path: Some(path),
-      search_dir: self.search_dir,
+      ..self
     })
   }
 
@@ -82,15 +157,79 @@ impl BuildParamsBuilder {
       )
     } else {
       Ok(Self {
-        output_path: self.output_path,
         search_dir: Some(path),
+        ..self
       })
     }
   }
 
+  /// Directory containing the Cargo.toml to read `[package.metadata.stylers]` from.
+  /// This has to be the crate using the macros, so that they agree on the class names
+  pub fn with_manifest_dir(self, path: Utf8PathBuf) -> color_eyre::Result<Self> {
+    if !path.join("Cargo.toml").is_file() {
+      bail!("Manifest dir {:?} does not contain a Cargo.toml", path)
+    } else {
+      Ok(Self {
+        manifest_dir: Some(path),
+        ..self
+      })
+    }
+  }
+
+  /// Overrides the class naming scheme instead of loading it from the manifest and environment.
+  /// Only useful if the macros are configured the same way
+  pub fn with_class_naming(self, class_naming: ClassNaming) -> Self {
+    Self {
+      class_naming: Some(class_naming),
+      ..self
+    }
+  }
+
+  /// Overrides the scoping of the selectors instead of loading it from the manifest and environment.
+  /// Only useful if the macros are configured the same way
+  pub fn with_scope_options(self, scope_options: ScopeOptions) -> Self {
+    Self {
+      scope_options: Some(scope_options),
+      ..self
+    }
+  }
+
+  /// Overrides the browsers to add vendor prefixes for instead of loading them from the manifest and environment
+  pub fn with_targets(self, targets: Targets) -> Self {
+    Self {
+      targets: Some(targets),
+      ..self
+    }
+  }
+
+  /// What to do when two different css bodies end up with the same class
+  pub fn with_on_collision(self, on_collision: OnCollision) -> Self {
+    Self {
+      on_collision: Some(on_collision),
+      ..self
+    }
+  }
+
+  /// Whether to minify the output, see [`stylers_core::ast::StyleSheet::minify`]
+  pub fn with_minify(self, minify: bool) -> Self {
+    Self { minify, ..self }
+  }
+
+  /// Pretty prints the output with one declaration per line, see [`stylers_core::ast::StyleSheet::to_pretty_string`].
+  /// Overrides `pretty` of the manifest and environment, which like for the macros only applies to debug builds
+  pub fn with_pretty(self, indent: Indent) -> Self {
+    Self {
+      pretty: Some(indent),
+      ..self
+    }
+  }
+
   /// Will error if appropriate defaults were not provided,
   /// or paths were not utf8 encoded
   pub fn finish(mut self) -> color_eyre::Result<BuildParams> {
+    if self.minify && self.pretty.is_some() {
+      bail!("The output can't be both minified and pretty printed");
+    }
     let output_path: Utf8PathBuf = match &self.output_path {
       Some(output_path) => output_path.clone(),
       None => {
@@ -114,9 +253,46 @@ impl BuildParamsBuilder {
         self.search_dir.unwrap()
       }
     };
+    let manifest_dir = match self.manifest_dir {
+      Some(manifest_dir) => manifest_dir,
+      None => current_dir()?.try_into()?,
+    };
+    let class_naming = match self.class_naming {
+      Some(class_naming) => class_naming,
+      None => ClassNaming::load(Some(manifest_dir.as_std_path()))
+        .map_err(|err| eyre!(err))
+        .wrap_err("Couldn't load the class naming configuration")?,
+    };
+    let scope_options = match self.scope_options {
+      Some(scope_options) => scope_options,
+      None => ScopeOptions::load(Some(manifest_dir.as_std_path()))
+        .map_err(|err| eyre!(err))
+        .wrap_err("Couldn't load the scoping configuration")?,
+    };
+    let targets = match self.targets {
+      Some(targets) => targets,
+      None => Targets::load(Some(manifest_dir.as_std_path()))
+        .map_err(|err| eyre!(err))
+        .wrap_err("Couldn't load the browser targets")?,
+    };
+    // a minified output isn't pretty printed, whatever the configuration says.
+    let pretty = match self.pretty {
+      Some(indent) => Some(indent),
+      None if self.minify || env::var("PROFILE").is_ok_and(|profile| profile == "release") => None,
+      None => Indent::load(Some(manifest_dir.as_std_path()))
+        .map_err(|err| eyre!(err))
+        .wrap_err("Couldn't load the pretty printing configuration")?,
+    };
     Ok(BuildParams {
       output_path,
       search_dir,
+      manifest_dir,
+      class_naming,
+      scope_options,
+      targets,
+      on_collision: self.on_collision.unwrap_or_default(),
+      minify: self.minify,
+      pretty,
     })
   }
 }
@@ -128,12 +304,21 @@ pub fn build(build_params: BuildParams) -> color_eyre::Result<()> {
   color_eyre::install().ok();
 
   let pattern = format!("{}/**/*.rs", build_params.search_dir);
+  // the configuration has to be tracked like the macros track it, so that both agree on the classes.
+  rerun_if("changed", &build_params.search_dir);
+  rerun_if("changed", build_params.manifest_dir.join("Cargo.toml"));
+  for var in CONFIG_ENV_VARS {
+    rerun_if("env-changed", var);
+  }
 
   info!(search_pattern = %pattern, output_file = %build_params.output_path, "Building stylers css output");
   let mut files_counter = Saturating(0u128);
   let mut macros_couter = Saturating(0u32);
 
   let mut output_css = String::from("");
+  let mut emitted_classes = EmittedClasses::default();
+  let mut collisions = vec![];
+  let mut css_errors = vec![];
   p!(
     "{}",
     "===============================Stylers debug output start==============================="
@@ -165,29 +350,83 @@ pub fn build(build_params: BuildParams) -> color_eyre::Result<()> {
 
     files_counter += 1;
 
+    // `module` is set for `css_module!`, which takes either inline css or the path of a css file.
     enum MacroType {
-      Style,
-      StyleSheet,
+      Style { module: bool },
+      StyleSheet { module: bool },
     }
     #[derive(Default)]
     struct MacroVisitor<'ast> {
-      macros_collected: Vec<(MacroType, &'ast Macro)>,
+      // with the index of the function containing the macro, if any.
+      macros_collected: Vec<(MacroType, &'ast Macro, Option<usize>)>,
+      // name of every function with the markup of its `view!` or `rsx!` macros, if it has any.
+      functions: Vec<(String, Option<Markup>)>,
+      // functions being visited, the innermost one is last.
+      current_functions: Vec<usize>,
+    }
+
+    impl MacroVisitor<'_> {
+      fn visit_function(&mut self, name: &syn::Ident, visit: impl FnOnce(&mut Self)) {
+        self.current_functions.push(self.functions.len());
+        self.functions.push((name.to_string(), None));
+        visit(self);
+        self.current_functions.pop();
+      }
     }
 
     impl<'ast> syn::visit::Visit<'ast> for MacroVisitor<'ast> {
+      fn visit_item_fn(&mut self, item_fn: &'ast syn::ItemFn) {
+        self.visit_function(&item_fn.sig.ident, |visitor| {
+          syn::visit::visit_item_fn(visitor, item_fn)
+        });
+      }
+
+      fn visit_impl_item_fn(&mut self, impl_item_fn: &'ast syn::ImplItemFn) {
+        self.visit_function(&impl_item_fn.sig.ident, |visitor| {
+          syn::visit::visit_impl_item_fn(visitor, impl_item_fn)
+        });
+      }
+
+      fn visit_macro(&mut self, mac: &'ast Macro) {
+        if let Some(final_seg) = mac.path.segments.last()
+          && MARKUP_MACROS.contains(&final_seg.ident.to_string().as_str())
+          && let Some(&function) = self.current_functions.last()
+        {
+          self.functions[function]
+            .1
+            .get_or_insert_default()
+            .collect(mac.tokens.clone());
+        }
+      }
+
       fn visit_expr_macro(&mut self, expr_macro: &'ast syn::ExprMacro) {
+        self.visit_macro(&expr_macro.mac);
         let Some(final_seg) = expr_macro.mac.path.segments.last() else {
           return;
         };
+        let function = self.current_functions.last().copied();
         let macro_name = final_seg.ident.clone().to_string();
-        if macro_name == *"style" {
-          self
-            .macros_collected
-            .push((MacroType::Style, &expr_macro.mac));
+        if macro_name == *"style" || macro_name == *"scoped_style" {
+          self.macros_collected.push((
+            MacroType::Style { module: false },
+            &expr_macro.mac,
+            function,
+          ));
         } else if macro_name == *"style_sheet" {
+          self.macros_collected.push((
+            MacroType::StyleSheet { module: false },
+            &expr_macro.mac,
+            function,
+          ));
+        } else if macro_name == *"css_module" {
+          let macro_type = if expr_macro.mac.parse_body::<syn::LitStr>().is_ok() {
+            MacroType::StyleSheet { module: true }
+          } else {
+            MacroType::Style { module: true }
+          };
           self
             .macros_collected
-            .push((MacroType::StyleSheet, &expr_macro.mac));
+            .push((macro_type, &expr_macro.mac, function));
         }
       }
     }
@@ -196,33 +435,171 @@ pub fn build(build_params: BuildParams) -> color_eyre::Result<()> {
     let mut macros = MacroVisitor::default();
     macros.visit_file(&ast);
 
-    for (macro_type, mac) in macros.macros_collected.iter() {
-      match macro_type {
-        MacroType::Style => {
-          debug!(?file, "Processing `style` macro in file");
+    for (macro_type, mac, function) in macros.macros_collected.iter() {
+      let location = SourceLocation {
+        file: file.clone(),
+        line: mac.path.span().start().line,
+      };
+      // what the scoped selectors of a `style!` call refer to.
+      let mut sel_map = None;
+      let (class, css) = match *macro_type {
+        MacroType::Style { module } => {
+          debug!(?file, module, "Processing `style` macro in file");
           macros_couter += 1;
           let ts = mac.tokens.clone();
-          let class = Class::rand_class_from_seed(ts.to_string());
-          let token_stream = ts.into_iter();
-          let (scoped_css, _) = from_ts(token_stream, &class, false);
-          output_css += &scoped_css;
+          let naming = &build_params.class_naming;
+          let scope_options = &build_params.scope_options;
+          let hint = path_hint(&file);
+          let scoped = if module {
+            Class::from_module_ts(ts.clone().into_iter(), false, naming, scope_options, hint)
+              .and_then(|class| {
+                let (scoped_css, _) = module_from_ts(ts.into_iter(), &class, scope_options, false)?;
+                Ok((class, scoped_css))
+              })
+          } else {
+            Class::from_ts(ts.clone().into_iter(), false, naming, scope_options, hint).and_then(
+              |class| {
+                let (scoped_css, selectors) =
+                  from_ts(ts.into_iter(), &class, scope_options, false)?;
+                sel_map = Some(selectors);
+                Ok((class, scoped_css))
+              },
+            )
+          };
+          for warning in warnings_from_ts(mac.tokens.clone().into_iter(), false) {
+            let start = warning.span().start();
+            p!(
+              "{}:{}:{}: {}",
+              file.display(),
+              start.line,
+              start.column + 1,
+              warning
+            );
+            warn!(%location, %warning, "Css warning");
+          }
+          match scoped {
+            Ok(scoped) => scoped,
+            Err(errors) => {
+              for error in errors {
+                let start = error.span().start();
+                css_errors.push(format!(
+                  "{}:{}:{}: {}",
+                  file.display(),
+                  start.line,
+                  start.column + 1,
+                  error
+                ));
+              }
+              continue;
+            }
+          }
         }
-        MacroType::StyleSheet => {
-          debug!(?file, "Processing `style_sheet` macro in file");
+        MacroType::StyleSheet { module } => {
+          debug!(?file, module, "Processing `style_sheet` macro in file");
           macros_couter += 1;
           let ts = mac.tokens.clone();
           let file_path = ts.to_string();
           let file_path = file_path.trim_matches('"');
-          let css_content = std::fs::read_to_string(file_path).expect("Expected to read file");
+          let css_content = fs::read_to_string(file_path)
+            .wrap_err_with(|| format!("Error reading {} used in {}", file_path, location))?;
+          rerun_if("changed", file_path);
+          for warning in warnings_from_str(&css_content) {
+            p!("{}:{}", file_path, warning);
+            warn!(%location, %warning, "Css warning");
+          }
 
-          let class = Class::rand_class_from_seed(css_content.to_string());
-          let style = from_str(&css_content, &class);
-          output_css += &style;
+          let naming = &build_params.class_naming;
+          let scope_options = &build_params.scope_options;
+          let hint = path_hint(Path::new(file_path));
+          let scoped = if module {
+            Class::from_module_css(&css_content, naming, scope_options, hint).and_then(|class| {
+              let (style, _) = module_from_str(&css_content, &class, scope_options)?;
+              Ok((class, style))
+            })
+          } else {
+            Class::from_css(&css_content, naming, scope_options, hint).and_then(|class| {
+              let style = from_str(&css_content, &class, scope_options)?;
+              Ok((class, style))
+            })
+          };
+          match scoped {
+            Ok(scoped) => scoped,
+            Err(diagnostics) => {
+              for diagnostic in diagnostics {
+                css_errors.push(format!("{}:{}", file_path, diagnostic));
+              }
+              continue;
+            }
+          }
+        }
+      };
+
+      if let Some(sel_map) = sel_map
+        && let Some((function, Some(markup))) = function.map(|i| &macros.functions[i])
+      {
+        for selector in markup.unused(&sel_map) {
+          p!(
+            "{}: `{}` doesn't match anything in the markup of `fn {}`",
+            location,
+            selector,
+            function
+          );
+          warn!(%location, selector, function, "Unused selector");
         }
       }
+
+      let css = match prefix_css(&css, &build_params.targets) {
+        Ok(css) => css,
+        Err(diagnostics) => {
+          for diagnostic in diagnostics {
+            css_errors.push(format!("{}: generated css:{}", location, diagnostic));
+          }
+          continue;
+        }
+      };
+      match emitted_classes.record(class.as_name(), &css, location) {
+        Emitted::New => output_css += &css,
+        Emitted::Duplicate => {
+          debug!(
+            class = class.as_name(),
+            "Skipping css already emitted for this class"
+          );
+        }
+        Emitted::Collision(collision) => match build_params.on_collision {
+          OnCollision::Fail => collisions.push(collision),
+          OnCollision::Warn => {
+            p!("{}", collision);
+            warn!(%collision, "Class collision");
+            output_css += &css;
+          }
+        },
+      }
     }
   }
 
+  if !css_errors.is_empty() {
+    let mut report = eyre!("{} css error(s) found", css_errors.len());
+    for css_error in css_errors {
+      report = report.note(css_error);
+    }
+    return Err(report);
+  }
+
+  if !collisions.is_empty() {
+    let mut report = eyre!("{} class collision(s) found", collisions.len());
+    for collision in collisions {
+      report = report.note(collision.to_string());
+    }
+    return Err(report.suggestion(
+      "Change one of the colliding styles, or configure a longer hash in [package.metadata.stylers]",
+    ));
+  }
+
+  if build_params.minify {
+    output_css = minify_css(&output_css).map_err(output_css_errors)?;
+  } else if let Some(indent) = build_params.pretty {
+    output_css = pretty_css(&output_css, indent).map_err(output_css_errors)?;
+  }
   write_css(&build_params.output_path, &output_css).wrap_err("Error writing output CSS")?;
   // .unwrap_or_else(|e| p!("Problem creating output file: {}", e.to_string()));
 
@@ -234,6 +611,15 @@ pub fn build(build_params: BuildParams) -> color_eyre::Result<()> {
   Ok(())
 }
 
+// the output css is built from css which parsed before, failing to parse it again is a bug of stylers.
+fn output_css_errors(diagnostics: Vec<Diagnostic>) -> Report {
+  let mut report = eyre!("The output css doesn't parse again");
+  for diagnostic in diagnostics {
+    report = report.note(diagnostic.to_string());
+  }
+  report
+}
+
 /// Writes the styles in its own file and appends itself to the main.css file
 fn write_css(out_path: &Utf8Path, content: &str) -> io::Result<()> {
   let mut buffer = File::create(out_path)?;
@@ -0,0 +1,106 @@
+//! Tracks every class emitted by [`build`](super::build),
+//! so that two different css bodies sharing a class are caught at build time instead of in the browser.
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Where a `style!` or `style_sheet!` call was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SourceLocation {
+  pub(crate) file: PathBuf,
+  pub(crate) line: usize,
+}
+
+impl fmt::Display for SourceLocation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}:{}", self.file.display(), self.line)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Collision {
+  pub(crate) class: String,
+  pub(crate) first: SourceLocation,
+  pub(crate) second: SourceLocation,
+}
+
+impl fmt::Display for Collision {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "Class `{}` is used for different css at {} and {}",
+      self.class, self.first, self.second
+    )
+  }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Emitted {
+  /// First time this class was seen, its css has to be written.
+  New,
+  /// The same css was already written for this class, e.g. the same `style_sheet!` used twice.
+  Duplicate,
+  Collision(Collision),
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct EmittedClasses {
+  classes: HashMap<String, (String, SourceLocation)>,
+}
+
+impl EmittedClasses {
+  /// Records that `css` is emitted for `class`, found at `location`.
+  pub(crate) fn record(&mut self, class: &str, css: &str, location: SourceLocation) -> Emitted {
+    match self.classes.get(class) {
+      None => {
+        self
+          .classes
+          .insert(class.to_string(), (css.to_string(), location));
+        Emitted::New
+      }
+      Some((first_css, _)) if first_css == css => Emitted::Duplicate,
+      Some((_, first)) => Emitted::Collision(Collision {
+        class: class.to_string(),
+        first: first.clone(),
+        second: location,
+      }),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn location(line: usize) -> SourceLocation {
+    SourceLocation {
+      file: "src/lib.rs".into(),
+      line,
+    }
+  }
+
+  #[test]
+  fn detects_collisions() {
+    let mut emitted = EmittedClasses::default();
+    assert_eq!(
+      emitted.record("l-1", ".a.l-1{color: red;}", location(1)),
+      Emitted::New
+    );
+    assert_eq!(
+      emitted.record("l-2", ".a.l-2{color: red;}", location(2)),
+      Emitted::New
+    );
+    assert_eq!(
+      emitted.record("l-1", ".a.l-1{color: red;}", location(3)),
+      Emitted::Duplicate
+    );
+    let Emitted::Collision(collision) = emitted.record("l-1", ".b.l-1{color: red;}", location(4))
+    else {
+      panic!("Expected a collision");
+    };
+    assert_eq!(
+      collision.to_string(),
+      "Class `l-1` is used for different css at src/lib.rs:1 and src/lib.rs:4"
+    );
+  }
+}
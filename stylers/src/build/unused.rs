@@ -0,0 +1,201 @@
+//! Collects what the `view!` and `rsx!` markup of a function uses,
+//! so that [`build`](super::build) can warn about selectors of a `style!` call in the same function which match nothing.
+use std::collections::BTreeSet;
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use stylers_core::SelectorMap;
+
+/// Names of the markup macros, e.g. leptos `view!` and dioxus `rsx!`.
+pub(crate) const MARKUP_MACROS: &[&str] = &["view", "rsx"];
+
+/// Tags, classes and ids found in the markup of a function.
+#[derive(Debug, Default)]
+pub(crate) struct Markup {
+  tags: BTreeSet<String>,
+  classes: BTreeSet<String>,
+  ids: BTreeSet<String>,
+  /// A class is computed at runtime, e.g. `class=move || ...`, so any class might be used.
+  dynamic_classes: bool,
+  /// Same as `dynamic_classes` for ids.
+  dynamic_ids: bool,
+}
+
+impl Markup {
+  /// Adds what the body of a markup macro uses.
+  /// Both `<div class="a" id="b">` and `div { class: "a", id: "b" }` are understood,
+  /// as well as leptos `class:a=...` and `class=("a", ...)`.
+  pub(crate) fn collect(&mut self, tokens: TokenStream) {
+    let mut tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    // leptos `view! { class = class_name, ... }` only adds the class of the style.
+    if let [TokenTree::Ident(ident), TokenTree::Punct(punct), ..] = tokens.as_slice()
+      && ident == "class"
+      && punct.as_char() == '='
+      && let Some(comma) = tokens
+        .iter()
+        .position(|token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+    {
+      tokens.drain(..=comma);
+    }
+    self.collect_tokens(&tokens);
+  }
+
+  fn collect_tokens(&mut self, tokens: &[TokenTree]) {
+    for (i, token) in tokens.iter().enumerate() {
+      match token {
+        TokenTree::Group(group) => {
+          self.collect_tokens(&group.stream().into_iter().collect::<Vec<_>>())
+        }
+        TokenTree::Ident(ident) => {
+          let name = ident.to_string();
+          let previous = i.checked_sub(1).and_then(|i| tokens.get(i));
+          let next = tokens.get(i + 1);
+          if is_tag(&name)
+            && (matches!(previous, Some(TokenTree::Punct(punct)) if punct.as_char() == '<')
+              || matches!(next, Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace))
+          {
+            self.tags.insert(name.clone());
+          }
+          let Some(TokenTree::Punct(punct)) = next else {
+            continue;
+          };
+          match (name.as_str(), punct.as_char()) {
+            // leptos `class:name=condition`, the name may contain dashes.
+            ("class", ':') if matches!(tokens.get(i + 2), Some(TokenTree::Ident(_))) => {
+              self.classes.insert(dashed_name(&tokens[i + 2..]));
+            }
+            ("class", '=' | ':') => match tokens.get(i + 2) {
+              Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+                match group
+                  .stream()
+                  .into_iter()
+                  .next()
+                  .as_ref()
+                  .and_then(string_value)
+                {
+                  Some(class) => {
+                    self.classes.insert(class);
+                  }
+                  None => self.dynamic_classes = true,
+                }
+              }
+              value => match value.and_then(string_value) {
+                Some(classes) => self
+                  .classes
+                  .extend(classes.split_whitespace().map(str::to_string)),
+                None => self.dynamic_classes = true,
+              },
+            },
+            ("id", '=' | ':') => match tokens.get(i + 2).and_then(string_value) {
+              Some(id) => {
+                self.ids.insert(id);
+              }
+              None => self.dynamic_ids = true,
+            },
+            _ => {}
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  /// Selectors of the scoped style which can't match anything in the markup, e.g. `.card` or `#title`.
+  /// The ones only found inside of `:not()` aren't expected in the markup.
+  pub(crate) fn unused(&self, sel_map: &SelectorMap) -> Vec<String> {
+    let tags = sel_map
+      .tags
+      .iter()
+      .filter(|tag| !self.tags.contains(&tag.to_ascii_lowercase()))
+      .cloned();
+    let classes = sel_map
+      .classes
+      .iter()
+      .filter(|class| !self.dynamic_classes && !self.classes.contains(*class))
+      .map(|class| format!(".{}", class));
+    let ids = sel_map
+      .ids
+      .iter()
+      .filter(|id| !self.dynamic_ids && !self.ids.contains(*id))
+      .map(|id| format!("#{}", id));
+    tags
+      .chain(classes)
+      .chain(ids)
+      .filter(|selector| !sel_map.negated.contains(selector))
+      .collect()
+  }
+}
+
+// html and svg elements are lower case, components aren't.
+fn is_tag(name: &str) -> bool {
+  name.starts_with(|c: char| c.is_ascii_lowercase())
+    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// joins `is - open` back into `is-open`.
+fn dashed_name(tokens: &[TokenTree]) -> String {
+  let mut name = String::new();
+  for token in tokens {
+    match token {
+      TokenTree::Ident(ident) => name += &ident.to_string(),
+      TokenTree::Punct(punct) if punct.as_char() == '-' => name.push('-'),
+      _ => break,
+    }
+  }
+  name
+}
+
+// the value of a string literal, unless it is formatted at runtime like `"{class}"` in rsx.
+fn string_value(token: &TokenTree) -> Option<String> {
+  let TokenTree::Literal(literal) = token else {
+    return None;
+  };
+  let value = syn::parse2::<syn::LitStr>(TokenTree::Literal(literal.clone()).into())
+    .ok()?
+    .value();
+  (!value.contains('{')).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sel_map(tags: &[&str], classes: &[&str], ids: &[&str]) -> SelectorMap {
+    let set = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+    SelectorMap {
+      tags: set(tags),
+      classes: set(classes),
+      ids: set(ids),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn finds_unused_selectors() {
+    let mut markup = Markup::default();
+    markup.collect(
+      r#"class = class, <div class="card wide" id="title"><p class:is-open=open class=("active", on)>"Hi"</p><Button/></div>"#
+        .parse()
+        .unwrap(),
+    );
+    let sel_map = sel_map(
+      &["div", "p", "span"],
+      &["card", "is-open", "active", "old"],
+      &["title", "gone"],
+    );
+    assert_eq!(markup.unused(&sel_map), vec!["span", ".old", "#gone"]);
+
+    let mut markup = Markup::default();
+    markup.collect(
+      r#"div { class: "card", p { class: "{dynamic}" } }"#
+        .parse()
+        .unwrap(),
+    );
+    assert_eq!(markup.unused(&sel_map), vec!["span", "#gone", "#title"]);
+
+    let sel_map = SelectorMap {
+      negated: [".old", "span"].map(String::from).into_iter().collect(),
+      ..sel_map
+    };
+    assert_eq!(markup.unused(&sel_map), vec!["#gone", "#title"]);
+  }
+}
@@ -7,11 +7,17 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/build.rs"))]
 //! ```
 
+pub use stylers_macro::css_module;
+pub use stylers_macro::scoped_style;
+pub use stylers_macro::scoped_style_str;
 pub use stylers_macro::style;
 pub use stylers_macro::style_sheet;
 pub use stylers_macro::style_sheet_str;
 pub use stylers_macro::style_str;
 
+pub use scoped_style::ScopedStyle;
+mod scoped_style;
+
 #[cfg(feature = "build")]
 pub use build::*;
 #[cfg(feature = "build")]
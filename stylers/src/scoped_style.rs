@@ -0,0 +1,81 @@
+use std::fmt;
+
+/// Class of a `scoped_style!` or `scoped_style_str!` block together with the class and id names its scoped selectors refer to.
+/// It prints as the class, so it can be used wherever the class of `style!` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScopedStyle {
+  class: &'static str,
+  css: Option<&'static str>,
+  classes: &'static [&'static str],
+  ids: &'static [&'static str],
+}
+
+impl ScopedStyle {
+  /// Used by the macros, `classes` and `ids` have to be sorted.
+  #[doc(hidden)]
+  pub const fn new(
+    class: &'static str,
+    css: Option<&'static str>,
+    classes: &'static [&'static str],
+    ids: &'static [&'static str],
+  ) -> Self {
+    Self {
+      class,
+      css,
+      classes,
+      ids,
+    }
+  }
+
+  /// Generated class added to the component.
+  pub const fn class(&self) -> &'static str {
+    self.class
+  }
+
+  /// Scoped css, only returned by `scoped_style_str!` as `scoped_style!` writes it to the output file of `stylers::build`.
+  pub const fn css(&self) -> Option<&'static str> {
+    self.css
+  }
+
+  /// Sorted class names used in the selectors, e.g. `one` for `div.one`.
+  pub const fn classes(&self) -> &'static [&'static str] {
+    self.classes
+  }
+
+  /// Sorted ids used in the selectors, e.g. `two` for `#two`.
+  pub const fn ids(&self) -> &'static [&'static str] {
+    self.ids
+  }
+
+  pub fn has_class(&self, class: &str) -> bool {
+    self.classes.binary_search(&class).is_ok()
+  }
+
+  pub fn has_id(&self, id: &str) -> bool {
+    self.ids.binary_search(&id).is_ok()
+  }
+}
+
+impl fmt::Display for ScopedStyle {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.class)
+  }
+}
+
+impl AsRef<str> for ScopedStyle {
+  fn as_ref(&self) -> &str {
+    self.class
+  }
+}
+
+impl From<ScopedStyle> for &'static str {
+  fn from(style: ScopedStyle) -> Self {
+    style.class
+  }
+}
+
+impl From<ScopedStyle> for String {
+  fn from(style: ScopedStyle) -> Self {
+    style.class.to_string()
+  }
+}
@@ -0,0 +1,30 @@
+use stylers::css_module;
+
+#[test]
+fn exposes_renamed_classes() {
+  let styles = css_module! {
+      .button {
+          padding: 4px 8px;
+      }
+      .button.primary:hover .icon-left {
+          color: white;
+      }
+      .button :global(.tooltip), .type {
+          display: none;
+      }
+  };
+
+  let class = styles.button.strip_prefix("button-").unwrap();
+  assert_eq!(styles.primary, format!("primary-{}", class));
+  assert_eq!(styles.icon_left, format!("icon-left-{}", class));
+  assert_eq!(styles.r#type, format!("type-{}", class));
+}
+
+#[test]
+fn reads_css_files() {
+  let styles = css_module!("stylers/tests/samples/button.css");
+
+  let class = styles.button.strip_prefix("button-").unwrap();
+  assert_eq!(styles.primary, format!("primary-{}", class));
+  assert_eq!(styles.icon_left, format!("icon-left-{}", class));
+}
@@ -0,0 +1,37 @@
+use stylers::{scoped_style_str, style_str};
+
+#[test]
+fn exposes_referenced_names() {
+  let style = scoped_style_str! {
+      #title, div.card > .body {
+          color: red;
+      }
+      .card :deep(.third-party) {
+          margin: 0;
+      }
+      :global(.portal), body .icon:hover {
+          top: 0;
+      }
+  };
+  let (class, css) = style_str! {
+      #title, div.card > .body {
+          color: red;
+      }
+      .card :deep(.third-party) {
+          margin: 0;
+      }
+      :global(.portal), body .icon:hover {
+          top: 0;
+      }
+  };
+
+  assert_eq!(style.class(), class);
+  assert_eq!(style.to_string(), class);
+  assert_eq!(style.css(), Some(css));
+  assert_eq!(style.classes(), ["body", "card", "icon"]);
+  assert_eq!(style.ids(), ["title"]);
+  assert!(style.has_class("card"));
+  assert!(!style.has_class("third-party"));
+  assert!(!style.has_class("portal"));
+  assert!(style.has_id("title"));
+}
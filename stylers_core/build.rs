@@ -0,0 +1,211 @@
+//! Generates the property table of `src/style/properties.rs` from `data/properties.toml`,
+//! and the browser compat tables of `src/targets.rs` from `data/compat.toml`.
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+  let data = "data/properties.toml";
+  println!("cargo::rerun-if-changed={}", data);
+  let content = fs::read_to_string(data).expect("Expected to read the vendored css data");
+  let table: toml::Table = content
+    .parse()
+    .expect("Expected valid toml in the css data");
+
+  let mut properties = table["properties"]
+    .as_array()
+    .expect("Expected a properties array")
+    .iter()
+    .map(|property| {
+      let field = |name: &str| {
+        property
+          .get(name)
+          .unwrap_or_else(|| panic!("{} is missing", name))
+      };
+      (
+        field("name")
+          .as_str()
+          .expect("Expected a string name")
+          .to_string(),
+        field("inherited").as_bool().expect("Expected a boolean"),
+        field("shorthand").as_bool().expect("Expected a boolean"),
+        match field("status").as_str() {
+          Some("standard") => "Standard",
+          Some("experimental") => "Experimental",
+          Some("nonstandard") => "Nonstandard",
+          Some("obsolete") => "Obsolete",
+          status => panic!("Unknown status {:?}", status),
+        },
+      )
+    })
+    .collect::<Vec<_>>();
+  // sorted for binary search.
+  properties.sort();
+
+  let mut descriptors = table["descriptors"]
+    .as_table()
+    .expect("Expected a descriptors table")
+    .values()
+    .flat_map(|names| names.as_array().expect("Expected a descriptors array"))
+    .map(|name| name.as_str().expect("Expected a string descriptor"))
+    .collect::<Vec<_>>();
+  descriptors.sort();
+  descriptors.dedup();
+
+  let mut prefixes = table["prefixes"]
+    .as_table()
+    .expect("Expected a prefixes table")
+    .iter()
+    .map(|(name, prefixes)| {
+      let prefixes = prefixes
+        .as_array()
+        .expect("Expected a prefixes array")
+        .iter()
+        .map(|prefix| prefix.as_str().expect("Expected a string prefix"))
+        .collect::<Vec<_>>();
+      (name.as_str(), prefixes)
+    })
+    .collect::<Vec<_>>();
+  prefixes.sort();
+
+  let mut code = format!("static PROPERTIES: [Property; {}] = [\n", properties.len());
+  for (name, inherited, shorthand, status) in properties.iter() {
+    writeln!(
+      code,
+      "  Property {{ name: {:?}, inherited: {}, shorthand: {}, status: PropertyStatus::{} }},",
+      name, inherited, shorthand, status
+    )
+    .unwrap();
+  }
+  code += "];\n";
+  writeln!(
+    code,
+    "static DESCRIPTORS: [&str; {}] = {:?};",
+    descriptors.len(),
+    descriptors
+  )
+  .unwrap();
+
+  writeln!(
+    code,
+    "static PREFIXES: [(&str, &[&str]); {}] = [",
+    prefixes.len()
+  )
+  .unwrap();
+  for (name, prefixes) in prefixes.iter() {
+    writeln!(code, "  ({:?}, &{:?}),", name, prefixes).unwrap();
+  }
+  code += "];\n";
+
+  let out_dir = env::var("OUT_DIR").expect("Expected OUT_DIR to be set by cargo");
+  fs::write(Path::new(&out_dir).join("properties.rs"), code)
+    .expect("Expected to write the property table");
+  fs::write(Path::new(&out_dir).join("compat.rs"), compat_tables())
+    .expect("Expected to write the compat tables");
+}
+
+fn compat_tables() -> String {
+  let data = "data/compat.toml";
+  println!("cargo::rerun-if-changed={}", data);
+  let content = fs::read_to_string(data).expect("Expected to read the vendored compat data");
+  let table: toml::Table = content
+    .parse()
+    .expect("Expected valid toml in the compat data");
+  let versions = |versions: Option<&toml::Value>| {
+    versions
+      .map_or(&[][..], |versions| {
+        versions.as_array().expect("Expected a versions array")
+      })
+      .iter()
+      .map(|version| version_code(version.as_str().expect("Expected a string version")))
+      .collect::<Vec<_>>()
+      .join(", ")
+  };
+
+  let browsers = table["browsers"]
+    .as_table()
+    .expect("Expected a browsers table");
+  let mut code = format!("static BROWSERS: [BrowserData; {}] = [\n", browsers.len());
+  for (name, browser) in browsers.iter() {
+    let dead_until = browser
+      .get("dead-until")
+      .map_or("None".to_string(), |version| {
+        format!(
+          "Some({})",
+          version_code(version.as_str().expect("Expected a string version"))
+        )
+      });
+    writeln!(
+      code,
+      "  BrowserData {{ name: {:?}, versions: &[{}], esr: &[{}], dead_until: {} }},",
+      name,
+      versions(browser.get("versions")),
+      versions(browser.get("esr")),
+      dead_until
+    )
+    .unwrap();
+  }
+  code += "];\n";
+
+  for (key, name) in [
+    ("properties", "PROPERTY_VARIANTS"),
+    ("selectors", "SELECTOR_VARIANTS"),
+    ("at-rules", "AT_RULE_VARIANTS"),
+  ] {
+    let features = table[key]
+      .as_table()
+      .unwrap_or_else(|| panic!("Expected a {} table", key));
+    // sorted by feature for binary search, the variants of a feature keep their order.
+    let mut variants = features
+      .iter()
+      .flat_map(|(feature, variants)| {
+        variants
+          .as_table()
+          .expect("Expected a table of variants")
+          .iter()
+          .map(move |(variant, support)| (feature, variant, support))
+      })
+      .collect::<Vec<_>>();
+    variants.sort_by_key(|(feature, _, _)| *feature);
+    writeln!(code, "static {}: [Variant; {}] = [", name, variants.len()).unwrap();
+    for (feature, variant, support) in variants {
+      let support = support
+        .as_table()
+        .expect("Expected a table of browser versions")
+        .iter()
+        .map(|(browser, version)| {
+          assert!(
+            browsers.contains_key(browser),
+            "Unknown browser {}",
+            browser
+          );
+          match version.as_str().expect("Expected a string version") {
+            "never" => format!("({:?}, None)", browser),
+            version => format!("({:?}, Some({}))", browser, version_code(version)),
+          }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+      writeln!(
+        code,
+        "  Variant {{ feature: {:?}, variant: {:?}, standard_since: &[{}] }},",
+        feature, variant, support
+      )
+      .unwrap();
+    }
+    code += "];\n";
+  }
+  code
+}
+
+// `16.4` as `Version::new(16, 4)`.
+fn version_code(version: &str) -> String {
+  let (major, minor) = version.split_once('.').unwrap_or((version, "0"));
+  let number = |part: &str| {
+    part
+      .parse::<u16>()
+      .unwrap_or_else(|_| panic!("Invalid version {}", version))
+  };
+  format!("Version::new({}, {})", number(major), number(minor))
+}
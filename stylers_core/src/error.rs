@@ -0,0 +1,87 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+use std::fmt;
+
+/// Invalid css inside of a `style!` macro, pointing at the token it was found at.
+#[derive(Debug, Clone)]
+pub struct Error {
+  message: String,
+  span: Span,
+}
+
+impl Error {
+  pub fn new(message: impl Into<String>, span: Span) -> Self {
+    Self {
+      message: message.into(),
+      span,
+    }
+  }
+
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Span of the offending token. Outside of procedural macros its line and column are
+  /// only available with the `span-locations` feature of `proc-macro2`.
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
+  /// `compile_error!` invocation reporting this error at the offending token.
+  pub fn to_compile_error(&self) -> TokenStream {
+    let message = &self.message;
+    quote_spanned! { self.span => ::core::compile_error!(#message) }
+  }
+
+  /// Statement making the compiler warn about this at the offending token.
+  /// Stable rust has no `compile_warning!`, so this uses a deprecated item instead.
+  pub fn to_compile_warning(&self) -> TokenStream {
+    let message = &self.message;
+    quote_spanned! { self.span =>
+      {
+        #[deprecated(note = #message)]
+        struct CssWarning;
+        let _ = CssWarning;
+      }
+    }
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.message)
+  }
+}
+
+impl std::error::Error for Error {}
+
+/// Invalid css found in a css file, located by its line and column in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+  /// 1-based line number.
+  pub line: usize,
+  /// 1-based column, counted in characters.
+  pub column: usize,
+  pub message: String,
+}
+
+impl Diagnostic {
+  // locates the byte `offset` of the css.
+  pub(crate) fn at(css: &str, offset: usize, message: String) -> Self {
+    let before = &css[..offset.min(css.len())];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    Self {
+      line: before.matches('\n').count() + 1,
+      column: before[line_start..].chars().count() + 1,
+      message,
+    }
+  }
+}
+
+impl fmt::Display for Diagnostic {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}:{}: {}", self.line, self.column, self.message)
+  }
+}
+
+impl std::error::Error for Diagnostic {}
@@ -0,0 +1,98 @@
+//! Indentation of pretty printed css, see [`crate::ast::StyleSheet::to_pretty_string`].
+//! The `*_str!` macros pretty print the css they return in debug builds when it is configured
+//! in the `[package.metadata.stylers]` table of the crate's Cargo.toml or the environment variable, which takes precedence.
+//!
+//! ```toml
+//! [package.metadata.stylers]
+//! pretty = true # or a number of spaces, or "tab"
+//! ```
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::naming::{invalid_config, manifest_config};
+
+pub const PRETTY_ENV: &str = "STYLERS_PRETTY";
+
+/// Indentation of one level of nesting, two spaces by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+  Spaces(u8),
+  Tab,
+}
+
+impl Default for Indent {
+  fn default() -> Self {
+    Self::Spaces(2)
+  }
+}
+
+impl Indent {
+  /// Loads the indentation the crate in `manifest_dir` pretty prints with, `None` if it isn't configured or turned off.
+  /// The environment variable overrides `pretty` in `[package.metadata.stylers]`.
+  pub fn load(manifest_dir: Option<&Path>) -> Result<Option<Self>, String> {
+    if let Ok(pretty) = std::env::var(PRETTY_ENV) {
+      return match pretty.as_str() {
+        "" | "0" | "false" | "no" => Ok(None),
+        "1" | "true" | "yes" => Ok(Some(Self::default())),
+        indent => indent.parse().map(Some),
+      };
+    }
+    let Some(manifest_dir) = manifest_dir else {
+      return Ok(None);
+    };
+    let manifest_path = manifest_dir.join("Cargo.toml");
+    let Some(pretty) =
+      manifest_config(&manifest_path)?.and_then(|config| config.get("pretty").cloned())
+    else {
+      return Ok(None);
+    };
+    let invalid = || {
+      invalid_config(
+        &manifest_path,
+        "pretty",
+        "a boolean, a number of spaces or \"tab\"",
+      )
+    };
+    match pretty {
+      toml::Value::Boolean(pretty) => Ok(pretty.then(Self::default)),
+      toml::Value::Integer(spaces) => u8::try_from(spaces)
+        .map(|spaces| Some(Self::Spaces(spaces)))
+        .map_err(|_| invalid()),
+      toml::Value::String(indent) => indent.parse().map(Some).map_err(|_| invalid()),
+      _ => Err(invalid()),
+    }
+  }
+
+  /// [`Indent::load`] for the crate being compiled.
+  pub fn from_cargo_env() -> Result<Option<Self>, String> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    Self::load(manifest_dir.as_deref())
+  }
+}
+
+impl FromStr for Indent {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.trim() {
+      "tab" | "\t" => Ok(Self::Tab),
+      spaces => spaces.parse().map(Self::Spaces).map_err(|_| {
+        format!(
+          "Invalid indentation {:?}, expected a number of spaces or \"tab\"",
+          s
+        )
+      }),
+    }
+  }
+}
+
+/// Prints one level of indentation.
+impl fmt::Display for Indent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Spaces(spaces) => write!(f, "{:1$}", "", usize::from(*spaces)),
+      Self::Tab => f.write_str("\t"),
+    }
+  }
+}
@@ -1,19 +1,61 @@
-#![feature(extend_one)]
-#![feature(proc_macro_span)]
+mod error;
+mod indent;
+mod naming;
+mod parser;
+mod scope_config;
 mod style;
 mod style_sheet;
+mod targets;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, TokenTree};
 use quote::{ToTokens, quote};
-use rand::prelude::*;
-use rand_chacha::ChaCha8Rng;
-use std::collections::hash_map::RandomState;
-use std::hash::{BuildHasher, Hasher};
 
+pub use error::{Diagnostic, Error};
+pub use indent::{Indent, PRETTY_ENV};
+pub use naming::{ClassNaming, HashAlphabet, path_hint};
+pub use style::{ScopeOptions, ScopeStrategy, SelectorMap};
+pub use targets::{TARGETS_ENV, Targets, Version};
+/// Typed representation of the parsed css. Printing any node with [`std::fmt::Display`] gives its css text.
+pub mod ast {
+  pub use crate::style::{
+    AtRule, AtRuleBlock, AttributeOperator, AttributeSelector, Combinator, CompoundSelector,
+    Declaration, Rule, Selector, SelectorList, SimpleSelector, StyleDeclaration, StyleRule,
+    StyleSheet,
+  };
+}
+/// Css properties known to the validation, with their metadata from the css specs and MDN.
+pub mod properties {
+  pub use crate::style::properties::{
+    Property, PropertyStatus, VENDOR_PREFIXES, prefixes, properties, property, split_vendor_prefix,
+  };
+}
+pub use style::build_module_from_ts as module_from_ts;
 pub use style::build_style_from_ts as from_ts;
+pub use style::build_warnings_from_ts as warnings_from_ts;
+pub use style_sheet::build_module_from_str as module_from_str;
 pub use style_sheet::build_style_from_str as from_str;
+pub use style_sheet::build_warnings_from_str as warnings_from_str;
+pub use style_sheet::minify_css;
+pub use style_sheet::prefix_css;
+pub use style_sheet::pretty_css;
+
+/// Class used while normalizing css content before hashing it.
+/// Any fixed value works, it only has to be the same on every side computing a class.
+const NORMALIZATION_CLASS: &str = "stylers";
 
-//ref: https://rust-random.github.io/book/guide-seeding.html
+/// Environment variables overriding `[package.metadata.stylers]`.
+/// They change the output of the macros and of `stylers::build`, so both have to rerun when one of them changes.
+pub const CONFIG_ENV_VARS: &[&str] = &[
+  naming::PREFIX_ENV,
+  naming::HASH_LENGTH_ENV,
+  naming::HASH_ALPHABET_ENV,
+  naming::HINT_ENV,
+  scope_config::SCOPING_ENV,
+  scope_config::SCOPE_CUSTOM_PROPERTIES_ENV,
+  scope_config::PUBLIC_CUSTOM_PROPERTIES_ENV,
+  TARGETS_ENV,
+  PRETTY_ENV,
+];
 
 #[derive(Debug)]
 pub struct Class(String);
@@ -23,22 +65,73 @@ impl Class {
     Self(class)
   }
 
-  pub fn random() -> Self {
-    let hash = RandomState::new().build_hasher().finish();
+  /// Derives the class from a stable hash of already normalized css content.
+  /// Prefer [`Class::from_ts`] or [`Class::from_css`] which normalize the content first.
+  pub fn from_content(content: &str, naming: &ClassNaming, hint: Option<&str>) -> Self {
+    Self(naming.class_name(fnv1a(content.as_bytes()), hint))
+  }
+
+  /// Derives the class of a `style!` block from the css it compiles to.
+  /// Both the `style!` macro and `stylers::build` must use this so that they agree on the class.
+  pub fn from_ts(
+    token_stream: impl Iterator<Item = TokenTree>,
+    is_proc_macro: bool,
+    naming: &ClassNaming,
+    scope_options: &ScopeOptions,
+    hint: Option<&str>,
+  ) -> Result<Self, Vec<Error>> {
+    let normalization_class = Class::new(NORMALIZATION_CLASS.into());
+    let (css, _) = from_ts(
+      token_stream,
+      &normalization_class,
+      scope_options,
+      is_proc_macro,
+    )?;
+    Ok(Self::from_content(&css, naming, hint))
+  }
+
+  /// Derives the class of a `style_sheet!` file from the css it compiles to.
+  /// Both the `style_sheet!` macro and `stylers::build` must use this so that they agree on the class.
+  pub fn from_css(
+    style_str: &str,
+    naming: &ClassNaming,
+    scope_options: &ScopeOptions,
+    hint: Option<&str>,
+  ) -> Result<Self, Vec<Diagnostic>> {
+    let normalization_class = Class::new(NORMALIZATION_CLASS.into());
+    let css = from_str(style_str, &normalization_class, scope_options)?;
+    Ok(Self::from_content(&css, naming, hint))
+  }
 
-    Self(format!("l-{}", &hash.to_string()[0..6]))
+  /// Derives the class of a `css_module!` block from the css it compiles to.
+  /// Module classes differ from the `style!` classes of the same css.
+  pub fn from_module_ts(
+    token_stream: impl Iterator<Item = TokenTree>,
+    is_proc_macro: bool,
+    naming: &ClassNaming,
+    scope_options: &ScopeOptions,
+    hint: Option<&str>,
+  ) -> Result<Self, Vec<Error>> {
+    let normalization_class = Class::new(NORMALIZATION_CLASS.into());
+    let (css, _) = module_from_ts(
+      token_stream,
+      &normalization_class,
+      scope_options,
+      is_proc_macro,
+    )?;
+    Ok(Self::from_content(&css, naming, hint))
   }
 
-  pub fn rand_class_from_seed(content: String) -> Self {
-    let mut no_of_chars = 0;
-    for ch in content.chars() {
-      if !ch.is_whitespace() && !ch.is_whitespace() {
-        no_of_chars += 1;
-      }
-    }
-    let mut rng = ChaCha8Rng::seed_from_u64(no_of_chars);
-    let hash = rng.r#gen::<i32>();
-    Self(format!("l-{}", &hash.to_string()[0..6]))
+  /// Derives the class of a `css_module!` file from the css it compiles to.
+  pub fn from_module_css(
+    style_str: &str,
+    naming: &ClassNaming,
+    scope_options: &ScopeOptions,
+    hint: Option<&str>,
+  ) -> Result<Self, Vec<Diagnostic>> {
+    let normalization_class = Class::new(NORMALIZATION_CLASS.into());
+    let (css, _) = module_from_str(style_str, &normalization_class, scope_options)?;
+    Ok(Self::from_content(&css, naming, hint))
   }
 
   pub fn as_name(&self) -> &str {
@@ -56,3 +149,79 @@ impl ToTokens for Class {
     tokens.extend(quote! { #class })
   }
 }
+
+/// 64 bit FNV-1a. Unlike [`std::hash::DefaultHasher`] its output is fixed,
+/// so class names don't change between compiler versions or build hosts.
+/// ref: <http://www.isthe.com/chongo/tech/comp/fnv/index.html>
+fn fnv1a(bytes: &[u8]) -> u64 {
+  const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+  const PRIME: u64 = 0x100000001b3;
+
+  bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+    (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn same_length_content_gets_distinct_classes() {
+    let one = Class::from_css(
+      "button{color: red;}",
+      &ClassNaming::default(),
+      &ScopeOptions::default(),
+      None,
+    )
+    .unwrap();
+    let two = Class::from_css(
+      "button{color: tan;}",
+      &ClassNaming::default(),
+      &ScopeOptions::default(),
+      None,
+    )
+    .unwrap();
+    assert_ne!(one.as_name(), two.as_name());
+  }
+
+  #[test]
+  fn formatting_does_not_change_class() {
+    let one = Class::from_css(
+      "button{color: red;}",
+      &ClassNaming::default(),
+      &ScopeOptions::default(),
+      None,
+    )
+    .unwrap();
+    let two = Class::from_css(
+      "button {\n    color: red;\n}\n",
+      &ClassNaming::default(),
+      &ScopeOptions::default(),
+      None,
+    )
+    .unwrap();
+    assert_eq!(one.as_name(), two.as_name());
+  }
+
+  #[test]
+  fn token_and_string_paths_agree() {
+    let ts: TokenStream = "button { color: red; }".parse().unwrap();
+    let one = Class::from_ts(
+      ts.into_iter(),
+      false,
+      &ClassNaming::default(),
+      &ScopeOptions::default(),
+      None,
+    )
+    .unwrap();
+    let two = Class::from_css(
+      "button {\n    color: red;\n}\n",
+      &ClassNaming::default(),
+      &ScopeOptions::default(),
+      None,
+    )
+    .unwrap();
+    assert_eq!(one.as_name(), two.as_name());
+  }
+}
@@ -0,0 +1,361 @@
+//! Configuration of the generated class names.
+//! The same configuration has to be loaded by the macros and by `stylers::build`,
+//! so both read it from the `[package.metadata.stylers]` table of the crate's Cargo.toml
+//! and from environment variables, which take precedence.
+//!
+//! ```toml
+//! [package.metadata.stylers]
+//! prefix = "btn-"
+//! hash-length = 6
+//! hash-alphabet = "base62"
+//! hint = true
+//! ```
+//!
+//! The scoping of the selectors and the browsers to add vendor prefixes for are read from the same table,
+//! see [`crate::ScopeOptions`] and [`crate::Targets`].
+use std::path::{Path, PathBuf};
+
+pub const PREFIX_ENV: &str = "STYLERS_CLASS_PREFIX";
+pub const HASH_LENGTH_ENV: &str = "STYLERS_HASH_LENGTH";
+pub const HASH_ALPHABET_ENV: &str = "STYLERS_HASH_ALPHABET";
+pub const HINT_ENV: &str = "STYLERS_CLASS_HINT";
+
+/// Digits used to print the content hash of a class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlphabet {
+  Decimal,
+  Hex,
+  Base36,
+  Base62,
+}
+
+impl HashAlphabet {
+  const DIGITS: &'static [u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+  fn base(self) -> u64 {
+    match self {
+      HashAlphabet::Decimal => 10,
+      HashAlphabet::Hex => 16,
+      HashAlphabet::Base36 => 36,
+      HashAlphabet::Base62 => 62,
+    }
+  }
+
+  /// Longest hash which still only contains digits derived from the 64 bit content hash.
+  fn max_length(self) -> usize {
+    match self {
+      HashAlphabet::Decimal => 19,
+      HashAlphabet::Hex => 16,
+      HashAlphabet::Base36 => 12,
+      HashAlphabet::Base62 => 10,
+    }
+  }
+
+  fn encode(self, mut hash: u64, length: usize) -> String {
+    let base = self.base();
+    let mut digits = Vec::with_capacity(length);
+    for _ in 0..length {
+      digits.push(Self::DIGITS[(hash % base) as usize]);
+      hash /= base;
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+  }
+}
+
+impl std::str::FromStr for HashAlphabet {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "decimal" => Ok(HashAlphabet::Decimal),
+      "hex" => Ok(HashAlphabet::Hex),
+      "base36" => Ok(HashAlphabet::Base36),
+      "base62" => Ok(HashAlphabet::Base62),
+      _ => Err(format!(
+        "Unknown hash alphabet {:?}, expected one of decimal, hex, base36 or base62",
+        s
+      )),
+    }
+  }
+}
+
+/// How class names are built: `{prefix}{hint-}{hash}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassNaming {
+  prefix: String,
+  hash_length: usize,
+  hash_alphabet: HashAlphabet,
+  hint: bool,
+}
+
+impl Default for ClassNaming {
+  fn default() -> Self {
+    Self {
+      prefix: "l-".into(),
+      hash_length: 8,
+      hash_alphabet: HashAlphabet::Hex,
+      hint: false,
+    }
+  }
+}
+
+impl ClassNaming {
+  /// Loads the naming scheme of the crate in `manifest_dir`, or the defaults if there is none.
+  /// Values from environment variables override the ones in `[package.metadata.stylers]`.
+  pub fn load(manifest_dir: Option<&Path>) -> Result<Self, String> {
+    let mut naming = match manifest_dir {
+      Some(manifest_dir) => Self::from_manifest(&manifest_dir.join("Cargo.toml"))?,
+      None => Self::default(),
+    };
+    naming.apply_env(|key| std::env::var(key).ok())?;
+    naming.validate()?;
+    Ok(naming)
+  }
+
+  /// Loads the naming scheme of the crate currently being compiled, using `CARGO_MANIFEST_DIR`.
+  pub fn from_cargo_env() -> Result<Self, String> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    Self::load(manifest_dir.as_deref())
+  }
+
+  pub fn with_prefix(mut self, prefix: impl Into<String>) -> Result<Self, String> {
+    self.prefix = prefix.into();
+    self.validate()?;
+    Ok(self)
+  }
+
+  pub fn with_hash_length(mut self, hash_length: usize) -> Result<Self, String> {
+    self.hash_length = hash_length;
+    self.validate()?;
+    Ok(self)
+  }
+
+  pub fn with_hash_alphabet(mut self, hash_alphabet: HashAlphabet) -> Result<Self, String> {
+    self.hash_alphabet = hash_alphabet;
+    self.validate()?;
+    Ok(self)
+  }
+
+  pub fn with_hint(mut self, hint: bool) -> Self {
+    self.hint = hint;
+    self
+  }
+
+  pub fn prefix(&self) -> &str {
+    &self.prefix
+  }
+
+  /// Builds the class name from the content hash.
+  /// `hint` is a human readable name of the component (e.g. the file stem), only used if enabled.
+  pub fn class_name(&self, hash: u64, hint: Option<&str>) -> String {
+    let mut name = self.prefix.clone();
+    if self.hint
+      && let Some(hint) = hint.map(sanitize_hint).filter(|hint| !hint.is_empty())
+    {
+      name.push_str(&hint);
+      name.push('-');
+    }
+    name.push_str(&self.hash_alphabet.encode(hash, self.hash_length));
+    name
+  }
+
+  fn from_manifest(manifest_path: &Path) -> Result<Self, String> {
+    let mut naming = Self::default();
+    let Some(config) = manifest_config(manifest_path)? else {
+      return Ok(naming);
+    };
+
+    let invalid = |key: &str, expected: &str| invalid_config(manifest_path, key, expected);
+    if let Some(prefix) = config.get("prefix") {
+      naming.prefix = prefix
+        .as_str()
+        .ok_or_else(|| invalid("prefix", "a string"))?
+        .to_string();
+    }
+    if let Some(hash_length) = config.get("hash-length") {
+      naming.hash_length = hash_length
+        .as_integer()
+        .and_then(|len| usize::try_from(len).ok())
+        .ok_or_else(|| invalid("hash-length", "a positive integer"))?;
+    }
+    if let Some(hash_alphabet) = config.get("hash-alphabet") {
+      naming.hash_alphabet = hash_alphabet
+        .as_str()
+        .ok_or_else(|| invalid("hash-alphabet", "a string"))?
+        .parse()?;
+    }
+    if let Some(hint) = config.get("hint") {
+      naming.hint = hint.as_bool().ok_or_else(|| invalid("hint", "a boolean"))?;
+    }
+    Ok(naming)
+  }
+
+  fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+    if let Some(prefix) = var(PREFIX_ENV) {
+      self.prefix = prefix;
+    }
+    if let Some(hash_length) = var(HASH_LENGTH_ENV) {
+      self.hash_length = hash_length
+        .parse()
+        .map_err(|_| format!("Expected {} to be a positive integer", HASH_LENGTH_ENV))?;
+    }
+    if let Some(hash_alphabet) = var(HASH_ALPHABET_ENV) {
+      self.hash_alphabet = hash_alphabet.parse()?;
+    }
+    if let Some(hint) = var(HINT_ENV) {
+      self.hint = matches!(hint.as_str(), "1" | "true" | "yes");
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<(), String> {
+    // the prefix also guards against class names starting with a digit, which aren't valid selectors.
+    let mut chars = self.prefix.chars();
+    let valid_start = match chars.next() {
+      Some('-') => {
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '-')
+      }
+      Some(c) => c.is_ascii_alphabetic() || c == '_',
+      None => false,
+    };
+    if !valid_start
+      || !self
+        .prefix
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+      return Err(format!(
+        "Class prefix {:?} must start with a letter, `_` or `-` followed by a letter and only contain letters, digits, `_` and `-`",
+        self.prefix
+      ));
+    }
+    let max_length = self.hash_alphabet.max_length();
+    if self.hash_length == 0 || self.hash_length > max_length {
+      return Err(format!(
+        "Hash length must be between 1 and {} for the {:?} alphabet, got {}",
+        max_length, self.hash_alphabet, self.hash_length
+      ));
+    }
+    Ok(())
+  }
+}
+
+/// The `[package.metadata.stylers]` table of the manifest, if the manifest exists and has one.
+pub(crate) fn manifest_config(manifest_path: &Path) -> Result<Option<toml::Value>, String> {
+  let Ok(manifest) = std::fs::read_to_string(manifest_path) else {
+    return Ok(None);
+  };
+  let manifest: toml::Table = manifest
+    .parse()
+    .map_err(|err| format!("Couldn't parse {:?}: {}", manifest_path, err))?;
+  Ok(
+    manifest
+      .get("package")
+      .and_then(|package| package.get("metadata"))
+      .and_then(|metadata| metadata.get("stylers"))
+      .cloned(),
+  )
+}
+
+/// Human readable hint for the classes of a source file, see [`ClassNaming::class_name`].
+/// It is the file stem, or the name of the directory for `mod.rs`, `lib.rs` and `main.rs`,
+/// e.g. `src/button/mod.rs` gives `button` and `src/bin/admin/main.rs` gives `admin`.
+/// The root of a crate, e.g. `src/lib.rs`, doesn't get a hint.
+pub fn path_hint(path: &Path) -> Option<&str> {
+  let stem = path.file_stem()?.to_str()?;
+  if !matches!(stem, "mod" | "lib" | "main") {
+    return Some(stem);
+  }
+  path
+    .parent()?
+    .file_name()?
+    .to_str()
+    .filter(|dir| !matches!(*dir, "src" | "bin"))
+}
+
+pub(crate) fn invalid_config(manifest_path: &Path, key: &str, expected: &str) -> String {
+  format!(
+    "Expected `{}` in [package.metadata.stylers] of {:?} to be {}",
+    key, manifest_path, expected
+  )
+}
+
+fn sanitize_hint(hint: &str) -> String {
+  hint
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || c == '-' {
+        c
+      } else {
+        '-'
+      }
+    })
+    .collect::<String>()
+    .trim_matches('-')
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn default_naming() {
+    let naming = ClassNaming::default();
+    assert_eq!(naming.class_name(0xdeadbeef, Some("button")), "l-deadbeef");
+  }
+
+  #[test]
+  fn configured_naming() {
+    let naming = ClassNaming::default()
+      .with_prefix("btn-")
+      .unwrap()
+      .with_hash_alphabet(HashAlphabet::Base62)
+      .unwrap()
+      .with_hash_length(4)
+      .unwrap()
+      .with_hint(true);
+    assert_eq!(
+      naming.class_name(61, Some("my_button")),
+      "btn-my-button-000Z"
+    );
+  }
+
+  #[test]
+  fn env_overrides() {
+    let mut naming = ClassNaming::default();
+    naming
+      .apply_env(|key| match key {
+        PREFIX_ENV => Some("app-".into()),
+        HASH_ALPHABET_ENV => Some("decimal".into()),
+        HASH_LENGTH_ENV => Some("3".into()),
+        _ => None,
+      })
+      .unwrap();
+    assert_eq!(naming.class_name(1234, None), "app-234");
+  }
+
+  #[test]
+  fn path_hints() {
+    for (path, hint) in [
+      ("src/button.rs", Some("button")),
+      ("/app/src/button/mod.rs", Some("button")),
+      ("src/bin/admin/main.rs", Some("admin")),
+      ("crates/ui/src/lib.rs", None),
+      ("src/main.rs", None),
+      ("src/bin/main.rs", None),
+      ("lib.rs", None),
+    ] {
+      assert_eq!(path_hint(Path::new(path)), hint, "{}", path);
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_config() {
+    assert!(ClassNaming::default().with_prefix("").is_err());
+    assert!(ClassNaming::default().with_prefix("1a").is_err());
+    assert!(ClassNaming::default().with_prefix("a.b").is_err());
+    assert!(ClassNaming::default().with_hash_length(17).is_err());
+  }
+}
@@ -0,0 +1,697 @@
+//! Css parser shared by the `style!` macros and the css files of `style_sheet!`.
+//! Both hand it the css as text, so identical css always gives an identical [`StyleSheet`].
+//! It follows <https://www.w3.org/TR/css-syntax-3/#parsing> and skips what it can't parse while reporting an error for it.
+pub(crate) mod tokenizer;
+
+use std::ops::Range;
+
+use crate::style::css_style_declar::{standard_property, validate_property};
+use crate::style::values::validate_value;
+use crate::style::{
+  AtRule, AtRuleBlock, Declaration, Rule, ScopeStrategy, SelectorList, StyleDeclaration, StyleRule,
+  StyleSheet,
+};
+use tokenizer::{Token, TokenKind, tokenize};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ParseError {
+  pub(crate) message: String,
+  /// Byte range of the offending text in the parsed css.
+  pub(crate) range: Range<usize>,
+  pub(crate) severity: Severity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+  Error,
+  /// Valid css which is likely a mistake, e.g. a vendor prefixed property without its standard property.
+  Warning,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ParseOptions {
+  /// Report unknown properties and invalid values.
+  pub(crate) validate_properties: bool,
+  /// Report them as warnings instead of errors, css files haven't been validated before.
+  pub(crate) validation_warnings: bool,
+}
+
+/// Parses the css into a style sheet. Parsing doesn't stop at the first error, all of them are returned
+/// together with the warnings.
+pub(crate) fn parse(css: &str, options: ParseOptions) -> (StyleSheet, Vec<ParseError>) {
+  let tokens = tokenize(css);
+  let mut parser = Parser {
+    css,
+    tokens: &tokens,
+    pos: 0,
+    depth: 0,
+    scoping: None,
+    validation_severity: if options.validation_warnings {
+      Severity::Warning
+    } else {
+      Severity::Error
+    },
+    errors: vec![],
+  };
+  let nodes = parser.consume_nodes(None);
+  let rules = parser.parse_rules(&nodes, options.validate_properties);
+  let mut errors = parser.errors;
+  errors.sort_by_key(|error| error.range.start);
+  (
+    StyleSheet {
+      rules,
+      scoping: parser.scoping,
+    },
+    errors,
+  )
+}
+
+// component value, see https://www.w3.org/TR/css-syntax-3/#component-value
+#[derive(Debug)]
+enum Node {
+  Token(Token),
+  /// `{...}`, `(...)` or `[...]`, the kind is the one of the opening token.
+  Block {
+    kind: TokenKind,
+    children: Vec<Node>,
+    range: Range<usize>,
+  },
+  /// `name(...)`, the name range includes the opening parenthesis.
+  Function {
+    name: Range<usize>,
+    children: Vec<Node>,
+    range: Range<usize>,
+  },
+}
+
+impl Node {
+  fn kind(&self) -> Option<TokenKind> {
+    match self {
+      Node::Token(token) => Some(token.kind),
+      _ => None,
+    }
+  }
+
+  fn is_whitespace(&self) -> bool {
+    self.kind() == Some(TokenKind::Whitespace)
+  }
+
+  fn is_curly_block(&self) -> bool {
+    matches!(
+      self,
+      Node::Block {
+        kind: TokenKind::OpenCurly,
+        ..
+      }
+    )
+  }
+
+  fn range(&self) -> Range<usize> {
+    match self {
+      Node::Token(token) => token.range.clone(),
+      Node::Block { range, .. } | Node::Function { range, .. } => range.clone(),
+    }
+  }
+}
+
+fn closing_kind(kind: TokenKind) -> TokenKind {
+  match kind {
+    TokenKind::OpenCurly => TokenKind::CloseCurly,
+    TokenKind::OpenSquare => TokenKind::CloseSquare,
+    _ => TokenKind::CloseParen,
+  }
+}
+
+fn trim_whitespace(mut nodes: &[Node]) -> &[Node] {
+  while let [first, rest @ ..] = nodes
+    && first.is_whitespace()
+  {
+    nodes = rest;
+  }
+  while let [rest @ .., last] = nodes
+    && last.is_whitespace()
+  {
+    nodes = rest;
+  }
+  nodes
+}
+
+struct Parser<'a> {
+  css: &'a str,
+  tokens: &'a [Token],
+  pos: usize,
+  // number of blocks around the rules being parsed.
+  depth: usize,
+  scoping: Option<ScopeStrategy>,
+  // severity of unknown properties and invalid values.
+  validation_severity: Severity,
+  errors: Vec<ParseError>,
+}
+
+impl Parser<'_> {
+  fn error(&mut self, message: impl Into<String>, range: Range<usize>) {
+    self.errors.push(ParseError {
+      message: message.into(),
+      range,
+      severity: Severity::Error,
+    });
+  }
+
+  fn warning(&mut self, message: impl Into<String>, range: Range<usize>) {
+    self.errors.push(ParseError {
+      message: message.into(),
+      range,
+      severity: Severity::Warning,
+    });
+  }
+
+  fn invalid(&mut self, message: impl Into<String>, range: Range<usize>) {
+    self.errors.push(ParseError {
+      message: message.into(),
+      range,
+      severity: self.validation_severity,
+    });
+  }
+
+  fn text(&self, range: Range<usize>) -> &str {
+    &self.css[range]
+  }
+
+  // groups the tokens into blocks and functions until the `closing` token, given with the range of its opening token.
+  fn consume_nodes(&mut self, closing: Option<(TokenKind, Range<usize>)>) -> Vec<Node> {
+    let mut nodes = vec![];
+    while let Some(token) = self.tokens.get(self.pos).cloned() {
+      self.pos += 1;
+      match token.kind {
+        kind
+          if closing
+            .as_ref()
+            .is_some_and(|(closing, _)| *closing == kind) =>
+        {
+          return nodes;
+        }
+        TokenKind::OpenCurly | TokenKind::OpenParen | TokenKind::OpenSquare => {
+          let children = self.consume_nodes(Some((closing_kind(token.kind), token.range.clone())));
+          let end = self
+            .tokens
+            .get(self.pos - 1)
+            .map_or(self.css.len(), |t| t.range.end);
+          nodes.push(Node::Block {
+            kind: token.kind,
+            children,
+            range: token.range.start..end,
+          });
+        }
+        TokenKind::Function => {
+          let children = self.consume_nodes(Some((TokenKind::CloseParen, token.range.clone())));
+          let end = self
+            .tokens
+            .get(self.pos - 1)
+            .map_or(self.css.len(), |t| t.range.end);
+          nodes.push(Node::Function {
+            range: token.range.start..end,
+            name: token.range,
+            children,
+          });
+        }
+        TokenKind::CloseCurly | TokenKind::CloseParen | TokenKind::CloseSquare => {
+          self.error(
+            format!("Unexpected `{}`", self.text(token.range.clone())),
+            token.range,
+          );
+        }
+        // kept to go on parsing, but they wouldn't parse the same once printed.
+        TokenKind::BadString => {
+          self.error("Unterminated string", token.range.clone());
+          nodes.push(Node::Token(token));
+        }
+        TokenKind::BadUrl => {
+          self.error(
+            if self.text(token.range.clone()).ends_with(')') {
+              "Invalid url, it has to be quoted if it contains quotes, parentheses or whitespace"
+            } else {
+              "Unterminated url"
+            },
+            token.range.clone(),
+          );
+          nodes.push(Node::Token(token));
+        }
+        _ => nodes.push(Node::Token(token)),
+      }
+    }
+    if let Some((closing, opening)) = closing {
+      let expected = match closing {
+        TokenKind::CloseCurly => '}',
+        TokenKind::CloseSquare => ']',
+        _ => ')',
+      };
+      self.error(
+        format!("Expected `{}` before the end of the css", expected),
+        opening,
+      );
+    }
+    nodes
+  }
+
+  // list of rules of a style sheet or of at-rules like `@media`.
+  fn parse_rules(&mut self, nodes: &[Node], validate: bool) -> Vec<Rule> {
+    let mut rules = vec![];
+    let mut i = 0;
+    while i < nodes.len() {
+      match nodes[i].kind() {
+        Some(TokenKind::Whitespace | TokenKind::Cdo | TokenKind::Cdc) => i += 1,
+        Some(TokenKind::Semicolon) => {
+          self.error("Unexpected `;`", nodes[i].range());
+          i += 1;
+        }
+        Some(TokenKind::AtKeyword) => {
+          let (at_rule, end) = self.parse_at_rule(nodes, i, validate, false);
+          rules.extend(at_rule.map(Rule::AtRule));
+          i = end;
+        }
+        _ => {
+          let Some(block) = (i..nodes.len()).find(|&j| nodes[j].is_curly_block()) else {
+            let range = nodes[i].range().start..nodes[nodes.len() - 1].range().end;
+            self.error("Expected a `{` block after the selector", range);
+            break;
+          };
+          if let Some(style_rule) = self.parse_style_rule(&nodes[i..=block], validate) {
+            rules.push(Rule::StyleRule(style_rule));
+          }
+          i = block + 1;
+        }
+      }
+    }
+    rules
+  }
+
+  // at-rule starting at nodes[start], returns it with the index following it.
+  // The custom `@scoping` rule isn't returned, it sets the scoping strategy of the style sheet instead.
+  // nested: whether the at-rule is inside of a declaration block, where at-rules contain declarations too.
+  fn parse_at_rule(
+    &mut self,
+    nodes: &[Node],
+    start: usize,
+    validate: bool,
+    nested: bool,
+  ) -> (Option<AtRule>, usize) {
+    let name = self.text(nodes[start].range())[1..].to_string();
+    let end = (start + 1..nodes.len())
+      .find(|&j| nodes[j].kind() == Some(TokenKind::Semicolon) || nodes[j].is_curly_block())
+      .unwrap_or(nodes.len());
+    let prelude = self.serialize(&nodes[start + 1..end]);
+    if name == "scoping" {
+      let range = nodes[start].range();
+      if self.depth > 0 || nodes.get(end).is_some_and(Node::is_curly_block) {
+        self.error(
+          "`@scoping` must be a top level rule like `@scoping root;`",
+          range,
+        );
+      } else {
+        match prelude.parse() {
+          Ok(scoping) => self.scoping = Some(scoping),
+          Err(message) => self.error(message, range),
+        }
+      }
+      return (None, end + 1);
+    }
+    self.depth += 1;
+    let block = match nodes.get(end) {
+      Some(Node::Block { children, .. }) => Some(if AtRule::has_declaration_block(&name) {
+        AtRuleBlock::Declarations(self.parse_declarations(children, false))
+      } else if nested {
+        AtRuleBlock::Declarations(self.parse_declarations(children, validate))
+      } else {
+        AtRuleBlock::Rules(self.parse_rules(children, validate))
+      }),
+      _ => None,
+    };
+    self.depth -= 1;
+    (
+      Some(AtRule {
+        name,
+        prelude,
+        block,
+      }),
+      end + 1,
+    )
+  }
+
+  // selector followed by its block.
+  fn parse_style_rule(&mut self, nodes: &[Node], validate: bool) -> Option<StyleRule> {
+    let [
+      prelude @ ..,
+      Node::Block {
+        children, range, ..
+      },
+    ] = nodes
+    else {
+      return None;
+    };
+    let selector_text = self.serialize(prelude);
+    if selector_text.is_empty() {
+      self.error(
+        "Expected a selector before the block",
+        range.start..range.start + 1,
+      );
+      return None;
+    }
+    self.depth += 1;
+    let style = self.parse_declarations(children, validate);
+    self.depth -= 1;
+    let selectors = match SelectorList::parse(&selector_text) {
+      Ok(selectors) => selectors,
+      Err(error) => {
+        let prelude = trim_whitespace(prelude);
+        let range = match (prelude.first(), prelude.last()) {
+          (Some(first), Some(last)) => first.range().start..last.range().end,
+          _ => range.start..range.start + 1,
+        };
+        self.error(error.message(), range);
+        return None;
+      }
+    };
+    Some(StyleRule { selectors, style })
+  }
+
+  // contents of a block which may mix declarations and nested rules.
+  fn parse_declarations(&mut self, nodes: &[Node], validate: bool) -> StyleDeclaration {
+    let mut style = StyleDeclaration::default();
+    // vendor prefixed declarations of standard properties with the range of their property name.
+    let mut prefixed = vec![];
+    let mut i = 0;
+    while i < nodes.len() {
+      match nodes[i].kind() {
+        Some(TokenKind::Whitespace | TokenKind::Semicolon) => i += 1,
+        Some(TokenKind::AtKeyword) => {
+          let (at_rule, end) = self.parse_at_rule(nodes, i, validate, true);
+          style.rules.extend(at_rule.map(Rule::AtRule));
+          i = end;
+        }
+        _ => {
+          let end = (i..nodes.len())
+            .find(|&j| nodes[j].kind() == Some(TokenKind::Semicolon) || nodes[j].is_curly_block())
+            .unwrap_or(nodes.len());
+          if nodes.get(end).is_some_and(Node::is_curly_block) {
+            if let Some(style_rule) = self.parse_style_rule(&nodes[i..=end], validate) {
+              style.rules.push(Rule::StyleRule(style_rule));
+            }
+          } else if let Some(declaration) = self.parse_declaration(&nodes[i..end], validate) {
+            if standard_property(&declaration.property).is_some() {
+              let start = trim_whitespace(&nodes[i..end])[0].range().start;
+              prefixed.push((
+                declaration.property.clone(),
+                start..start + declaration.property.len(),
+              ));
+            }
+            style.declarations.push(declaration);
+          }
+          i = end + 1;
+        }
+      }
+    }
+    for (property, range) in prefixed {
+      let standard = standard_property(&property).unwrap_or_default();
+      if !style
+        .declarations
+        .iter()
+        .any(|declaration| declaration.property == standard)
+      {
+        self.warning(
+          format!(
+            "`{}` is used without the standard `{}` property",
+            property, standard
+          ),
+          range,
+        );
+      }
+    }
+    style
+  }
+
+  fn parse_declaration(&mut self, nodes: &[Node], validate: bool) -> Option<Declaration> {
+    let nodes = trim_whitespace(nodes);
+    let [Node::Token(property), rest @ ..] = nodes else {
+      let range = nodes.first()?.range();
+      self.error("Expected a property name", range);
+      return None;
+    };
+    if property.kind != TokenKind::Ident {
+      self.error("Expected a property name", property.range.clone());
+      return None;
+    }
+    let property_name = self.text(property.range.clone()).to_string();
+    let rest = trim_whitespace(rest);
+    let [colon, value @ ..] = rest else {
+      self.error(
+        format!("Expected `:` after `{}`", property_name),
+        property.range.clone(),
+      );
+      return None;
+    };
+    if colon.kind() != Some(TokenKind::Colon) {
+      self.error(
+        format!("Expected `:` after `{}`", property_name),
+        colon.range(),
+      );
+      return None;
+    }
+
+    let mut value = trim_whitespace(value);
+    let mut important = false;
+    if let [rest @ .., Node::Token(last)] = value
+      && last.kind == TokenKind::Ident
+      && self
+        .text(last.range.clone())
+        .eq_ignore_ascii_case("important")
+      && let [rest @ .., bang] = trim_whitespace(rest)
+      && bang.kind() == Some(TokenKind::Delim('!'))
+    {
+      value = trim_whitespace(rest);
+      important = true;
+    }
+    // custom properties are the only ones which may be empty.
+    if value.is_empty() && !property_name.starts_with("--") {
+      self.error(
+        format!("Expected a value for `{}`", property_name),
+        colon.range(),
+      );
+      return None;
+    }
+
+    if validate {
+      let (is_valid, suggest) = validate_property(&property_name);
+      if !is_valid {
+        self.invalid(
+          format!(
+            "Did you mean to use {} property",
+            suggest.unwrap_or_default()
+          ),
+          property.range.clone(),
+        );
+      } else if let (Some(first), Some(last)) = (value.first(), value.last())
+        // a missing semicolon is reported instead.
+        && !value
+          .iter()
+          .any(|node| node.kind() == Some(TokenKind::Colon))
+      {
+        let start = first.range().start;
+        let text = self.text(start..last.range().end);
+        if let Err((range, message)) = validate_value(&property_name, text) {
+          self.invalid(message, start + range.start..start + range.end);
+        }
+      }
+    }
+    // a colon inside of the value means the next declaration started without a semicolon.
+    if let Some(colon) = value
+      .iter()
+      .position(|node| node.kind() == Some(TokenKind::Colon))
+      && let Some(next_property) = value[..colon]
+        .iter()
+        .rev()
+        .find(|node| !node.is_whitespace())
+    {
+      self.error(
+        format!(
+          "Missing semicolon before `{}`",
+          self.text(next_property.range())
+        ),
+        next_property.range(),
+      );
+    }
+
+    Some(Declaration {
+      property: property_name,
+      value: self.serialize(value),
+      important,
+    })
+  }
+
+  // css text of the nodes with comments removed and whitespace collapsed to single spaces.
+  fn serialize(&self, nodes: &[Node]) -> String {
+    let mut text = String::new();
+    self.write_nodes(nodes, &mut text);
+    text
+  }
+
+  fn write_nodes(&self, nodes: &[Node], text: &mut String) {
+    let mut started = false;
+    let mut pending_space = false;
+    for node in nodes {
+      if node.is_whitespace() {
+        pending_space = true;
+        continue;
+      }
+      if pending_space && started {
+        text.push(' ');
+      }
+      pending_space = false;
+      started = true;
+      match node {
+        Node::Token(token) => text.push_str(self.text(token.range.clone())),
+        Node::Block { kind, children, .. } => {
+          let (open, close) = match kind {
+            TokenKind::OpenCurly => ('{', '}'),
+            TokenKind::OpenSquare => ('[', ']'),
+            _ => ('(', ')'),
+          };
+          text.push(open);
+          self.write_nodes(children, text);
+          text.push(close);
+        }
+        Node::Function { name, children, .. } => {
+          text.push_str(self.text(name.clone()));
+          self.write_nodes(children, text);
+          text.push(')');
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn print(css: &str) -> String {
+    let (style_sheet, errors) = parse(css, ParseOptions::default());
+    assert_eq!(errors, vec![]);
+    style_sheet.to_string()
+  }
+
+  #[test]
+  fn normalizes_whitespace_and_comments() {
+    assert_eq!(
+      print(
+        "/* a */ div  >\n p {\n  margin : 0   auto ;/* b */ font: calc( 1px +  2px ) a , b !important }"
+      ),
+      "div>p{margin: 0 auto;font: calc(1px + 2px) a , b !important;}"
+    );
+  }
+
+  #[test]
+  fn keeps_strings_as_written() {
+    assert_eq!(
+      print(r#"a[title="x }; y"]{content: "a;b}" ; background: url(a;b.png)}"#),
+      r#"a[title="x }; y"]{content: "a;b}";background: url(a;b.png);}"#
+    );
+  }
+
+  #[test]
+  fn parses_declaration_blocks_of_at_rules() {
+    assert_eq!(
+      print(
+        "@keyframes spin { from { top: 0 } 50% { top: 1px } }@page :first { margin: 1in; @top-left { content: 'x' } }"
+      ),
+      "@keyframes spin{from{top: 0;}50%{top: 1px;}}@page :first{margin: 1in;@top-left{content: 'x';}}"
+    );
+  }
+
+  #[test]
+  fn reports_errors_and_continues() {
+    let css = "p { colr: red\n margin: 0 } } div {}";
+    let (style_sheet, errors) = parse(
+      css,
+      ParseOptions {
+        validate_properties: true,
+        validation_warnings: false,
+      },
+    );
+    assert_eq!(style_sheet.to_string(), "p{colr: red margin: 0;}div{}");
+    let messages: Vec<_> = errors
+      .iter()
+      .map(|error| (error.message.as_str(), &css[error.range.clone()]))
+      .collect();
+    assert_eq!(
+      messages,
+      vec![
+        ("Did you mean to use color property", "colr"),
+        ("Missing semicolon before `margin`", "margin"),
+        ("Unexpected `}`", "}"),
+      ]
+    );
+  }
+
+  #[test]
+  fn reads_scoping_rule() {
+    let css = "@scoping root;\np {} @media print { @scoping last; } @scoping none;";
+    let (style_sheet, errors) = parse(css, ParseOptions::default());
+    assert_eq!(style_sheet.scoping, Some(ScopeStrategy::Root));
+    assert_eq!(style_sheet.to_string(), "p{}@media print{}");
+    let messages: Vec<_> = errors.iter().map(|error| error.message.as_str()).collect();
+    assert_eq!(
+      messages,
+      vec![
+        "`@scoping` must be a top level rule like `@scoping root;`",
+        "Unknown scoping strategy \"none\", expected one of every, root or last",
+      ]
+    );
+  }
+
+  #[test]
+  fn warns_about_prefixes_without_validation() {
+    let css = "p { -webkit-transform: none; colr: red }";
+    let (_, errors) = parse(css, ParseOptions::default());
+    let messages: Vec<_> = errors
+      .iter()
+      .map(|error| (error.message.as_str(), error.severity))
+      .collect();
+    assert_eq!(
+      messages,
+      vec![(
+        "`-webkit-transform` is used without the standard `transform` property",
+        Severity::Warning
+      )]
+    );
+  }
+
+  #[test]
+  fn reports_unterminated_strings_and_blocks() {
+    for (css, expected) in [
+      ("@import \"a.css", vec![("Unterminated string", "\"a.css")]),
+      (
+        "@import 'a\nb';",
+        vec![("Unterminated string", "'a"), ("Unterminated string", "';")],
+      ),
+      ("@import url(a.css", vec![("Unterminated url", "url(a.css")]),
+      (
+        "p {} @media (min-width: 1px",
+        vec![("Expected `)` before the end of the css", "(")],
+      ),
+      (
+        "@font-face { src: 'a",
+        vec![
+          ("Expected `}` before the end of the css", "{"),
+          ("Unterminated string", "'a"),
+        ],
+      ),
+    ] {
+      let (_, errors) = parse(css, ParseOptions::default());
+      let messages: Vec<_> = errors
+        .iter()
+        .map(|error| (error.message.as_str(), &css[error.range.clone()]))
+        .collect();
+      assert_eq!(messages, expected, "{:?}", css);
+    }
+  }
+}
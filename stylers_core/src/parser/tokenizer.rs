@@ -0,0 +1,409 @@
+//! Tokenizer following <https://www.w3.org/TR/css-syntax-3/#tokenization>.
+//! Tokens only keep their kind and byte range, the text is always read back from the source.
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+  Ident,
+  /// name followed by an opening parenthesis, e.g. `rotate(`
+  Function,
+  AtKeyword,
+  Hash,
+  String,
+  BadString,
+  Url,
+  BadUrl,
+  Delim(char),
+  Number,
+  Percentage,
+  Dimension,
+  /// Whitespace and comments.
+  Whitespace,
+  Cdo,
+  Cdc,
+  Colon,
+  Semicolon,
+  Comma,
+  OpenSquare,
+  CloseSquare,
+  OpenParen,
+  CloseParen,
+  OpenCurly,
+  CloseCurly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Token {
+  pub(crate) kind: TokenKind,
+  pub(crate) range: Range<usize>,
+}
+
+pub(crate) fn tokenize(css: &str) -> Vec<Token> {
+  let mut tokenizer = Tokenizer { css, pos: 0 };
+  let mut tokens = vec![];
+  while tokenizer.pos < css.len() {
+    let start = tokenizer.pos;
+    let kind = tokenizer.consume_token();
+    tokens.push(Token {
+      kind,
+      range: start..tokenizer.pos,
+    });
+  }
+  tokens
+}
+
+struct Tokenizer<'a> {
+  css: &'a str,
+  pos: usize,
+}
+
+fn is_ident_start(c: char) -> bool {
+  c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
+
+fn is_ident_char(c: char) -> bool {
+  is_ident_start(c) || c.is_ascii_digit() || c == '-'
+}
+
+fn is_valid_escape(first: Option<char>, second: Option<char>) -> bool {
+  first == Some('\\') && second.is_some_and(|c| c != '\n')
+}
+
+impl Tokenizer<'_> {
+  fn peek_nth(&self, n: usize) -> Option<char> {
+    self.css[self.pos..].chars().nth(n)
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.peek_nth(0)
+  }
+
+  fn bump(&mut self) -> Option<char> {
+    let c = self.peek()?;
+    self.pos += c.len_utf8();
+    Some(c)
+  }
+
+  fn starts_with(&self, s: &str) -> bool {
+    self.css[self.pos..].starts_with(s)
+  }
+
+  fn consume_token(&mut self) -> TokenKind {
+    if self.starts_with("/*") {
+      return self.consume_comment();
+    }
+    let Some(c) = self.bump() else {
+      return TokenKind::Whitespace;
+    };
+    match c {
+      c if c.is_whitespace() => {
+        while self.peek().is_some_and(char::is_whitespace) {
+          self.bump();
+        }
+        TokenKind::Whitespace
+      }
+      '"' | '\'' => self.consume_string(c),
+      '#' => {
+        if self.peek().is_some_and(is_ident_char) || is_valid_escape(self.peek(), self.peek_nth(1))
+        {
+          self.consume_name();
+          TokenKind::Hash
+        } else {
+          TokenKind::Delim('#')
+        }
+      }
+      '(' => TokenKind::OpenParen,
+      ')' => TokenKind::CloseParen,
+      '[' => TokenKind::OpenSquare,
+      ']' => TokenKind::CloseSquare,
+      '{' => TokenKind::OpenCurly,
+      '}' => TokenKind::CloseCurly,
+      ',' => TokenKind::Comma,
+      ':' => TokenKind::Colon,
+      ';' => TokenKind::Semicolon,
+      '+' | '.' if self.starts_number(Some(c)) => self.consume_numeric(),
+      '-' if self.starts_number(Some(c)) => self.consume_numeric(),
+      '-' if self.starts_with("->") => {
+        self.pos += 2;
+        TokenKind::Cdc
+      }
+      '-' if self.starts_ident(self.pos - 1) => self.consume_ident_like(),
+      '<' if self.starts_with("!--") => {
+        self.pos += 3;
+        TokenKind::Cdo
+      }
+      '@' => {
+        if self.starts_ident(self.pos) {
+          self.consume_name();
+          TokenKind::AtKeyword
+        } else {
+          TokenKind::Delim('@')
+        }
+      }
+      '\\' if is_valid_escape(Some(c), self.peek()) => self.consume_ident_like(),
+      c if c.is_ascii_digit() => self.consume_numeric(),
+      c if is_ident_start(c) => self.consume_ident_like(),
+      c => TokenKind::Delim(c),
+    }
+  }
+
+  fn consume_comment(&mut self) -> TokenKind {
+    self.pos += 2;
+    match self.css[self.pos..].find("*/") {
+      Some(end) => self.pos += end + 2,
+      None => self.pos = self.css.len(),
+    }
+    TokenKind::Whitespace
+  }
+
+  // whether the chars at the byte position `from` start an identifier.
+  fn starts_ident(&self, from: usize) -> bool {
+    let mut chars = self.css[from..].chars();
+    let (first, second, third) = (chars.next(), chars.next(), chars.next());
+    match first {
+      Some('-') => {
+        second.is_some_and(|c| is_ident_start(c) || c == '-') || is_valid_escape(second, third)
+      }
+      Some('\\') => is_valid_escape(first, second),
+      Some(c) => is_ident_start(c),
+      None => false,
+    }
+  }
+
+  // whether the consumed `first` char and the next chars start a number.
+  fn starts_number(&self, first: Option<char>) -> bool {
+    let second = self.peek();
+    match first {
+      Some('+' | '-') => {
+        second.is_some_and(|c| c.is_ascii_digit())
+          || (second == Some('.') && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit()))
+      }
+      Some('.') => second.is_some_and(|c| c.is_ascii_digit()),
+      Some(c) => c.is_ascii_digit(),
+      None => false,
+    }
+  }
+
+  fn consume_escape(&mut self) {
+    // the backslash is already consumed.
+    if let Some(c) = self.bump()
+      && c.is_ascii_hexdigit()
+    {
+      for _ in 0..5 {
+        if self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+          self.bump();
+        }
+      }
+      if self.peek().is_some_and(char::is_whitespace) {
+        self.bump();
+      }
+    }
+  }
+
+  fn consume_name(&mut self) {
+    loop {
+      match self.peek() {
+        Some(c) if is_ident_char(c) => {
+          self.bump();
+        }
+        Some('\\') if is_valid_escape(Some('\\'), self.peek_nth(1)) => {
+          self.bump();
+          self.consume_escape();
+        }
+        _ => return,
+      }
+    }
+  }
+
+  // a string which isn't closed before a newline or the end of the css is a bad string.
+  fn consume_string(&mut self, quote: char) -> TokenKind {
+    loop {
+      match self.peek() {
+        None => return TokenKind::BadString,
+        Some(c) if c == quote => {
+          self.bump();
+          return TokenKind::String;
+        }
+        Some('\n') => return TokenKind::BadString,
+        Some('\\') => {
+          self.bump();
+          // escaped newlines continue the string.
+          self.bump();
+        }
+        Some(_) => {
+          self.bump();
+        }
+      }
+    }
+  }
+
+  fn consume_number(&mut self) {
+    if matches!(self.peek(), Some('+' | '-')) {
+      self.bump();
+    }
+    while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+      self.bump();
+    }
+    if self.peek() == Some('.') && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit()) {
+      self.bump();
+      while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+        self.bump();
+      }
+    }
+    if matches!(self.peek(), Some('e' | 'E')) {
+      let digits_at = if matches!(self.peek_nth(1), Some('+' | '-')) {
+        2
+      } else {
+        1
+      };
+      if self.peek_nth(digits_at).is_some_and(|c| c.is_ascii_digit()) {
+        for _ in 0..digits_at {
+          self.bump();
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+          self.bump();
+        }
+      }
+    }
+  }
+
+  fn consume_numeric(&mut self) -> TokenKind {
+    // the first char of the number has been consumed to decide the token type, so go back to it.
+    self.pos -= 1;
+    self.consume_number();
+    if self.starts_ident(self.pos) {
+      self.consume_name();
+      TokenKind::Dimension
+    } else if self.peek() == Some('%') {
+      self.bump();
+      TokenKind::Percentage
+    } else {
+      TokenKind::Number
+    }
+  }
+
+  fn consume_ident_like(&mut self) -> TokenKind {
+    // the first char of the name has been consumed to decide the token type, so go back to it.
+    let start = self.css[..self.pos]
+      .char_indices()
+      .next_back()
+      .map_or(0, |(i, _)| i);
+    self.pos = start;
+    self.consume_name();
+    let name = &self.css[start..self.pos];
+    if self.peek() != Some('(') {
+      return TokenKind::Ident;
+    }
+    self.bump();
+    if !name.eq_ignore_ascii_case("url") {
+      return TokenKind::Function;
+    }
+    let after_paren = self.pos;
+    while self.peek().is_some_and(char::is_whitespace) {
+      self.bump();
+    }
+    if matches!(self.peek(), Some('"' | '\'')) {
+      // quoted urls are regular functions with a string argument.
+      self.pos = after_paren;
+      return TokenKind::Function;
+    }
+    self.consume_url()
+  }
+
+  // an url which isn't closed before the end of the css is a bad url.
+  fn consume_url(&mut self) -> TokenKind {
+    loop {
+      match self.bump() {
+        None => return TokenKind::BadUrl,
+        Some(')') => return TokenKind::Url,
+        Some(c) if c.is_whitespace() => {
+          while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+          }
+          if self.peek() == Some(')') {
+            self.bump();
+            return TokenKind::Url;
+          }
+          return self.consume_bad_url();
+        }
+        Some('"' | '\'' | '(') => return self.consume_bad_url(),
+        Some('\\') => {
+          self.bump();
+        }
+        Some(_) => {}
+      }
+    }
+  }
+
+  fn consume_bad_url(&mut self) -> TokenKind {
+    loop {
+      match self.bump() {
+        None | Some(')') => return TokenKind::BadUrl,
+        Some('\\') => {
+          self.bump();
+        }
+        Some(_) => {}
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn kinds(css: &str) -> Vec<(TokenKind, &str)> {
+    tokenize(css)
+      .into_iter()
+      .map(|token| (token.kind, &css[token.range]))
+      .collect()
+  }
+
+  #[test]
+  fn tokenizes_declaration() {
+    assert_eq!(
+      kinds("a>b{margin:-1.5em 10% calc(1px + 2e3px)/*c*/}"),
+      vec![
+        (TokenKind::Ident, "a"),
+        (TokenKind::Delim('>'), ">"),
+        (TokenKind::Ident, "b"),
+        (TokenKind::OpenCurly, "{"),
+        (TokenKind::Ident, "margin"),
+        (TokenKind::Colon, ":"),
+        (TokenKind::Dimension, "-1.5em"),
+        (TokenKind::Whitespace, " "),
+        (TokenKind::Percentage, "10%"),
+        (TokenKind::Whitespace, " "),
+        (TokenKind::Function, "calc("),
+        (TokenKind::Dimension, "1px"),
+        (TokenKind::Whitespace, " "),
+        (TokenKind::Delim('+'), "+"),
+        (TokenKind::Whitespace, " "),
+        (TokenKind::Dimension, "2e3px"),
+        (TokenKind::CloseParen, ")"),
+        (TokenKind::Whitespace, "/*c*/"),
+        (TokenKind::CloseCurly, "}"),
+      ]
+    );
+  }
+
+  #[test]
+  fn tokenizes_strings_and_urls() {
+    assert_eq!(
+      kinds(r#"@import url(a.css) "x\"y" 'z;' #id --var"#),
+      vec![
+        (TokenKind::AtKeyword, "@import"),
+        (TokenKind::Whitespace, " "),
+        (TokenKind::Url, "url(a.css)"),
+        (TokenKind::Whitespace, " "),
+        (TokenKind::String, r#""x\"y""#),
+        (TokenKind::Whitespace, " "),
+        (TokenKind::String, "'z;'"),
+        (TokenKind::Whitespace, " "),
+        (TokenKind::Hash, "#id"),
+        (TokenKind::Whitespace, " "),
+        (TokenKind::Ident, "--var"),
+      ]
+    );
+  }
+}
@@ -0,0 +1,137 @@
+//! Configuration of the selectors the class is added to and of the scoping of custom properties.
+//! Like the class naming it changes the generated class names, so the macros and `stylers::build` load it the same way,
+//! from the `[package.metadata.stylers]` table of the crate's Cargo.toml and from environment variables, which take precedence.
+//!
+//! ```toml
+//! [package.metadata.stylers]
+//! scoping = "root"
+//! scope-custom-properties = true
+//! public-custom-properties = "--theme-"
+//! ```
+use std::path::{Path, PathBuf};
+
+use crate::naming::{invalid_config, manifest_config};
+use crate::style::{ScopeOptions, ScopeStrategy};
+
+pub const SCOPING_ENV: &str = "STYLERS_SCOPING";
+pub const SCOPE_CUSTOM_PROPERTIES_ENV: &str = "STYLERS_SCOPE_CUSTOM_PROPERTIES";
+pub const PUBLIC_CUSTOM_PROPERTIES_ENV: &str = "STYLERS_PUBLIC_CUSTOM_PROPERTIES";
+
+impl ScopeOptions {
+  /// Loads the scoping configuration of the crate in `manifest_dir`, or the defaults if there is none.
+  /// Values from environment variables override the ones in `[package.metadata.stylers]`.
+  pub fn load(manifest_dir: Option<&Path>) -> Result<Self, String> {
+    let mut options = match manifest_dir {
+      Some(manifest_dir) => Self::from_manifest(&manifest_dir.join("Cargo.toml"))?,
+      None => Self::default(),
+    };
+    options.apply_env(|key| std::env::var(key).ok())?;
+    options.validate()?;
+    Ok(options)
+  }
+
+  /// Loads the scoping configuration of the crate currently being compiled, using `CARGO_MANIFEST_DIR`.
+  pub fn from_cargo_env() -> Result<Self, String> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    Self::load(manifest_dir.as_deref())
+  }
+
+  pub fn with_strategy(mut self, strategy: ScopeStrategy) -> Self {
+    self.strategy = strategy;
+    self
+  }
+
+  /// Scopes custom properties, except for the ones starting with `public_prefix`.
+  pub fn with_custom_property_scoping(
+    mut self,
+    public_prefix: Option<String>,
+  ) -> Result<Self, String> {
+    self.scope_custom_properties = true;
+    self.public_custom_properties = public_prefix;
+    self.validate()?;
+    Ok(self)
+  }
+
+  fn from_manifest(manifest_path: &Path) -> Result<Self, String> {
+    let mut options = Self::default();
+    let Some(config) = manifest_config(manifest_path)? else {
+      return Ok(options);
+    };
+
+    let invalid = |key: &str, expected: &str| invalid_config(manifest_path, key, expected);
+    if let Some(scoping) = config.get("scoping") {
+      options.strategy = scoping
+        .as_str()
+        .ok_or_else(|| invalid("scoping", "a string"))?
+        .parse()?;
+    }
+    if let Some(scope_custom_properties) = config.get("scope-custom-properties") {
+      options.scope_custom_properties = scope_custom_properties
+        .as_bool()
+        .ok_or_else(|| invalid("scope-custom-properties", "a boolean"))?;
+    }
+    if let Some(public_prefix) = config.get("public-custom-properties") {
+      options.public_custom_properties = Some(
+        public_prefix
+          .as_str()
+          .ok_or_else(|| invalid("public-custom-properties", "a string"))?
+          .to_string(),
+      );
+    }
+    Ok(options)
+  }
+
+  fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+    if let Some(scoping) = var(SCOPING_ENV) {
+      self.strategy = scoping.parse()?;
+    }
+    if let Some(scope_custom_properties) = var(SCOPE_CUSTOM_PROPERTIES_ENV) {
+      self.scope_custom_properties =
+        matches!(scope_custom_properties.as_str(), "1" | "true" | "yes");
+    }
+    if let Some(public_prefix) = var(PUBLIC_CUSTOM_PROPERTIES_ENV) {
+      self.public_custom_properties = Some(public_prefix);
+    }
+    Ok(())
+  }
+
+  fn validate(&self) -> Result<(), String> {
+    if let Some(public_prefix) = &self.public_custom_properties
+      && !public_prefix.starts_with("--")
+    {
+      return Err(format!(
+        "Public custom property prefix {:?} must start with `--`",
+        public_prefix
+      ));
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn env_overrides() {
+    let mut options = ScopeOptions::default();
+    options
+      .apply_env(|key| match key {
+        SCOPING_ENV => Some("last".into()),
+        SCOPE_CUSTOM_PROPERTIES_ENV => Some("1".into()),
+        _ => None,
+      })
+      .unwrap();
+    assert_eq!(options.strategy, ScopeStrategy::Last);
+    assert!(options.scope_custom_properties);
+  }
+
+  #[test]
+  fn rejects_invalid_config() {
+    assert!(
+      ScopeOptions::default()
+        .with_custom_property_scoping(Some("theme-".into()))
+        .is_err()
+    );
+  }
+}
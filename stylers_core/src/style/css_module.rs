@@ -0,0 +1,32 @@
+use std::collections::BTreeMap;
+
+use crate::Class;
+use crate::style::StyleSheet;
+use crate::style::scoping::{ScopeOptions, ScopeStrategy, Scoper, SelectorMap, scope_rules};
+
+impl StyleSheet {
+  /// Scopes the style sheet as a css module: instead of adding the class to the selectors,
+  /// every class name is suffixed with it, e.g. `.btn:hover` becomes `.btn-l-xxxxxx:hover`.
+  /// Class names inside of `:global()`, `:deep()` and document root compounds like `body.dark` keep their name.
+  /// Keyframes and custom properties are scoped like [`StyleSheet::scope`] does, a `@scoping` rule has no effect.
+  /// Returns the suffixed name of every local class name.
+  pub fn scope_module(
+    &mut self,
+    class: &Class,
+    options: &ScopeOptions,
+  ) -> BTreeMap<String, String> {
+    self.flatten_nesting();
+    let mut names = BTreeMap::new();
+    scope_rules(
+      &mut self.rules,
+      &mut Scoper::Module(class, &mut names),
+      ScopeStrategy::Every,
+      &mut SelectorMap::default(),
+    );
+    self.scope_keyframes(class);
+    if options.scope_custom_properties {
+      self.scope_custom_properties(class, options.public_custom_properties.as_deref());
+    }
+    names
+  }
+}
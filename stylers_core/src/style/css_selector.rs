@@ -0,0 +1,767 @@
+use std::fmt;
+
+use proc_macro2::Span;
+
+use crate::Error;
+
+/// Comma separated list of selectors, e.g. `h2, a`.
+/// Ressource: <https://developer.mozilla.org/en-US/docs/Web/CSS/Selector_list>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectorList(pub Vec<Selector>);
+
+/// Complex selector made of compound selectors joined by combinators, e.g. `div > p.one`.
+/// Ressource: <https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_selectors/Selector_structure>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selector {
+  pub compounds: Vec<CompoundSelector>,
+}
+
+/// Sequence of simple selectors not separated by a combinator, e.g. `p.one:hover`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompoundSelector {
+  /// How this compound relates to the previous one, `None` for the first compound of a selector.
+  pub combinator: Option<Combinator>,
+  pub selectors: Vec<SimpleSelector>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combinator {
+  /// `a b`
+  Descendant,
+  /// `a > b`
+  Child,
+  /// `a + b`
+  NextSibling,
+  /// `a ~ b`
+  SubsequentSibling,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimpleSelector {
+  /// Type selector including its namespace prefix if any, e.g. `div` or `svg|a`.
+  Type(String),
+  /// `*`
+  Universal,
+  /// `&`, the parent rule's selector inside of a nested rule.
+  Nesting,
+  /// `.name`
+  Class(String),
+  /// `#name`
+  Id(String),
+  /// `[name]` or `[name op value]`, e.g. `[title~=flower]` or `[lang="en" i]`.
+  Attribute(AttributeSelector),
+  /// `:name` or `:name(args)`. Arguments are kept as written.
+  PseudoClass { name: String, args: Option<String> },
+  /// `::name` or `::name(args)`. Arguments are kept as written.
+  PseudoElement { name: String, args: Option<String> },
+}
+
+/// Ressource: <https://developer.mozilla.org/en-US/docs/Web/CSS/Attribute_selectors>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttributeSelector {
+  /// Namespace prefix, e.g. `xlink` in `[xlink|href]`. `*` matches any namespace and an empty one no namespace (`[|href]`).
+  pub namespace: Option<String>,
+  pub name: String,
+  pub operator: Option<AttributeOperator>,
+  /// Value as written, including its quotes if it is a string.
+  pub value: Option<String>,
+  /// `i` for case-insensitive or `s` for case-sensitive matching of the value.
+  pub modifier: Option<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeOperator {
+  /// `=`
+  Equals,
+  /// `~=`, one of the whitespace separated words.
+  Includes,
+  /// `|=`, the value or the value followed by `-`.
+  DashMatch,
+  /// `^=`
+  Prefix,
+  /// `$=`
+  Suffix,
+  /// `*=`
+  Substring,
+}
+
+impl SelectorList {
+  /// Parses the selector text of a style rule, e.g. `div .one, h2`.
+  /// Invalid selectors like `div >`, `.a ++ .b`, `.a,` or `#` are an error, the selector lists of
+  /// pseudo classes like `:is()` included. The error isn't located inside of the text, its span is the call site.
+  pub fn parse(selector_text: &str) -> Result<SelectorList, Error> {
+    let mut parser = SelectorParser {
+      chars: selector_text.chars().collect(),
+      pos: 0,
+    };
+    parser.parse_list()
+  }
+}
+
+impl SimpleSelector {
+  /// Arguments of the pseudo class `name` which are a list of selectors, e.g. `h1, h2` of `:is(h1, h2)`.
+  /// The arguments of `:has()` are relative selectors, e.g. `> img`.
+  /// Returned with the arguments written before the list, e.g. `2n+1 of ` of `:nth-child(2n+1 of .item)`.
+  /// `None` if the arguments aren't a selector list or it doesn't parse.
+  pub fn selector_args<'a>(name: &str, args: &'a str) -> Option<(&'a str, SelectorList)> {
+    let (before, list) = split_selector_args(name, args)?;
+    Some((before, SelectorList::parse(list).ok()?))
+  }
+}
+
+// arguments written before the selector list and the selector list, see `SimpleSelector::selector_args`.
+fn split_selector_args<'a>(name: &str, args: &'a str) -> Option<(&'a str, &'a str)> {
+  match name {
+    "is" | "where" | "not" | "has" | "matches" | "any" => Some(("", args)),
+    "nth-child" | "nth-last-child" => {
+      let lower = args.to_ascii_lowercase();
+      let (of, _) = lower.match_indices("of").find(|(i, _)| {
+        lower[..*i].ends_with(char::is_whitespace)
+          && lower[i + 2..].starts_with(char::is_whitespace)
+      })?;
+      let start = args.len() - args[of + 2..].trim_start().len();
+      Some(args.split_at(start))
+    }
+    _ => None,
+  }
+}
+
+impl CompoundSelector {
+  pub fn has_nesting(&self) -> bool {
+    self.selectors.contains(&SimpleSelector::Nesting)
+  }
+
+  /// Whether the compound selects the root of the document or of a shadow tree,
+  /// e.g. `html`, `body`, `:root` or `:host(.dark)`, which are outside of any component.
+  pub fn is_document_root(&self) -> bool {
+    self.selectors.iter().any(|selector| match selector {
+      SimpleSelector::Type(name) => {
+        name.eq_ignore_ascii_case("html") || name.eq_ignore_ascii_case("body")
+      }
+      SimpleSelector::PseudoClass { name, .. } => {
+        matches!(name.as_str(), "root" | "host" | "host-context")
+      }
+      _ => false,
+    })
+  }
+
+  pub fn pseudo_class(&self, name: &str) -> Option<&SimpleSelector> {
+    self
+      .selectors
+      .iter()
+      .find(|selector| matches!(selector, SimpleSelector::PseudoClass { name: n, .. } if n == name))
+  }
+}
+
+struct SelectorParser {
+  chars: Vec<char>,
+  pos: usize,
+}
+
+impl SelectorParser {
+  fn peek(&self) -> Option<char> {
+    self.chars.get(self.pos).copied()
+  }
+
+  fn peek_nth(&self, n: usize) -> Option<char> {
+    self.chars.get(self.pos + n).copied()
+  }
+
+  fn skip_whitespace(&mut self) -> bool {
+    let start = self.pos;
+    while self.peek().is_some_and(char::is_whitespace) {
+      self.pos += 1;
+    }
+    self.pos != start
+  }
+
+  fn error(&self, message: String) -> Error {
+    Error::new(message, Span::call_site())
+  }
+
+  fn parse_list(&mut self) -> Result<SelectorList, Error> {
+    let mut list = SelectorList::default();
+    loop {
+      let selector = self.parse_selector()?;
+      if selector.compounds.is_empty() {
+        return Err(self.error(match self.peek() {
+          Some(',') => "Expected a selector before `,`".to_string(),
+          _ if !list.0.is_empty() => "Expected a selector after `,`".to_string(),
+          _ => "Expected a selector".to_string(),
+        }));
+      }
+      list.0.push(selector);
+      if self.peek() == Some(',') {
+        self.pos += 1;
+      } else {
+        break;
+      }
+    }
+    Ok(list)
+  }
+
+  fn parse_selector(&mut self) -> Result<Selector, Error> {
+    let mut selector = Selector::default();
+    let mut combinator = None;
+    self.skip_whitespace();
+    loop {
+      let compound = self.parse_compound()?;
+      if !compound.is_empty() {
+        selector.compounds.push(CompoundSelector {
+          // a combinator before the first compound makes a relative selector, e.g. `> p` in a nested rule.
+          combinator: if selector.compounds.is_empty() {
+            combinator
+          } else {
+            Some(combinator.unwrap_or(Combinator::Descendant))
+          },
+          selectors: compound,
+        });
+        combinator = None;
+      }
+
+      let had_whitespace = self.skip_whitespace();
+      // a combinator which isn't followed by a compound, e.g. `div >` or `.a ++ .b`.
+      if let Some(combinator) = combinator
+        && matches!(self.peek(), None | Some(',' | '>' | '+' | '~'))
+      {
+        return Err(self.error(format!("Expected a selector after `{}`", combinator)));
+      }
+      match self.peek() {
+        None | Some(',') => break,
+        Some('>') => combinator = Some(Combinator::Child),
+        Some('+') => combinator = Some(Combinator::NextSibling),
+        Some('~') => combinator = Some(Combinator::SubsequentSibling),
+        Some(_) if had_whitespace => {
+          combinator = Some(Combinator::Descendant);
+          continue;
+        }
+        Some(_) => continue,
+      }
+      self.pos += 1;
+      self.skip_whitespace();
+    }
+    Ok(selector)
+  }
+
+  fn parse_compound(&mut self) -> Result<Vec<SimpleSelector>, Error> {
+    let mut selectors = vec![];
+    while let Some(c) = self.peek() {
+      match c {
+        '.' => {
+          self.pos += 1;
+          selectors.push(SimpleSelector::Class(self.parse_name(".")?));
+        }
+        '#' => {
+          self.pos += 1;
+          selectors.push(SimpleSelector::Id(self.parse_name("#")?));
+        }
+        '[' => {
+          self.pos += 1;
+          selectors.push(SimpleSelector::Attribute(self.parse_attribute()));
+        }
+        ':' => {
+          self.pos += 1;
+          let is_element = self.peek() == Some(':');
+          if is_element {
+            self.pos += 1;
+          }
+          let name = self.parse_name(if is_element { "::" } else { ":" })?;
+          let args = self.parse_args();
+          if !is_element
+            && let Some(args) = &args
+            && let Some((_, list)) = split_selector_args(&name, args)
+              // the custom pseudo classes which leave their selector unscoped.
+              .or_else(|| matches!(name.as_str(), "deep" | "global").then_some(("", args)))
+          {
+            SelectorList::parse(list)?;
+          }
+          selectors.push(if is_element {
+            SimpleSelector::PseudoElement { name, args }
+          } else {
+            SimpleSelector::PseudoClass { name, args }
+          });
+        }
+        '*' if self.peek_nth(1) == Some('|') && self.peek_nth(2) != Some('=') => {
+          self.pos += 2;
+          selectors.push(SimpleSelector::Type(format!("*|{}", self.parse_ident())));
+        }
+        '*' => {
+          self.pos += 1;
+          selectors.push(SimpleSelector::Universal);
+        }
+        '&' => {
+          self.pos += 1;
+          selectors.push(SimpleSelector::Nesting);
+        }
+        '|' if self.peek_nth(1) != Some('=') => {
+          self.pos += 1;
+          selectors.push(SimpleSelector::Type(format!("|{}", self.parse_ident())));
+        }
+        c if c.is_whitespace() || matches!(c, ',' | '>' | '+' | '~') => break,
+        // percentage of a keyframe selector, e.g. `50%` or `12.5%`.
+        c if c.is_ascii_digit() && self.percentage_len().is_some() => {
+          let len = self.percentage_len().unwrap_or_default();
+          selectors.push(SimpleSelector::Type(
+            self.chars[self.pos..self.pos + len].iter().collect(),
+          ));
+          self.pos += len;
+        }
+        _ => {
+          let mut name = self.parse_ident();
+          if name.is_empty() {
+            return Err(self.error(format!("Unexpected `{}` in the selector", c)));
+          }
+          if self.peek() == Some('|') && self.peek_nth(1) != Some('=') {
+            self.pos += 1;
+            name.push('|');
+            name.push_str(&self.parse_ident());
+          }
+          selectors.push(SimpleSelector::Type(name));
+        }
+      }
+    }
+    // a type selector has to come first, e.g. `div&` rather than `&div`.
+    if let Some(i) = (1..selectors.len()).find(|&i| {
+      matches!(
+        selectors[i],
+        SimpleSelector::Type(_) | SimpleSelector::Universal
+      )
+    }) {
+      return Err(self.error(match &selectors[i - 1] {
+        SimpleSelector::Nesting => format!(
+          "`&{}` isn't valid css nesting, `&` can't be joined with a name",
+          selectors[i]
+        ),
+        _ => format!(
+          "`{}` has to come first in its compound, type selectors can't follow other selectors",
+          selectors[i]
+        ),
+      }));
+    }
+    Ok(selectors)
+  }
+
+  // name following `.`, `#` or `:`, which can't be empty.
+  fn parse_name(&mut self, before: &str) -> Result<String, Error> {
+    let name = self.parse_ident();
+    if name.is_empty() {
+      return Err(self.error(format!("Expected a name after `{}`", before)));
+    }
+    Ok(name)
+  }
+
+  // length of the percentage at the current position, e.g. 4 for `12.5%`.
+  fn percentage_len(&self) -> Option<usize> {
+    let digits = self.chars[self.pos..]
+      .iter()
+      .take_while(|c| c.is_ascii_digit() || **c == '.')
+      .count();
+    (self.peek_nth(digits) == Some('%')).then_some(digits + 1)
+  }
+
+  fn parse_ident(&mut self) -> String {
+    let mut ident = String::new();
+    while let Some(c) = self.peek() {
+      if c == '\\' {
+        self.parse_escape(&mut ident);
+      } else if c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+        ident.push(c);
+        self.pos += 1;
+      } else {
+        break;
+      }
+    }
+    ident
+  }
+
+  // escape sequence starting at the backslash, e.g. `\:` or `\31 ` which ends with an optional whitespace.
+  fn parse_escape(&mut self, text: &mut String) {
+    text.push('\\');
+    self.pos += 1;
+    let Some(escaped) = self.peek() else {
+      return;
+    };
+    text.push(escaped);
+    self.pos += 1;
+    if escaped.is_ascii_hexdigit() {
+      for _ in 0..5 {
+        match self.peek() {
+          Some(c) if c.is_ascii_hexdigit() => {
+            text.push(c);
+            self.pos += 1;
+          }
+          _ => break,
+        }
+      }
+      if let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+        text.push(c);
+        self.pos += 1;
+      }
+    }
+  }
+
+  // quoted string starting at the opening quote, kept as written.
+  fn parse_string(&mut self, text: &mut String) {
+    let Some(quote) = self.peek() else {
+      return;
+    };
+    text.push(quote);
+    self.pos += 1;
+    while let Some(c) = self.peek() {
+      if c == '\\' {
+        self.parse_escape(text);
+        continue;
+      }
+      text.push(c);
+      self.pos += 1;
+      if c == quote {
+        break;
+      }
+    }
+  }
+
+  // contents of an attribute selector following the opening bracket, up to and including the closing bracket.
+  fn parse_attribute(&mut self) -> AttributeSelector {
+    let mut attribute = AttributeSelector::default();
+    self.skip_whitespace();
+    match (self.peek(), self.peek_nth(1)) {
+      (Some('*'), Some('|')) => {
+        attribute.namespace = Some("*".into());
+        self.pos += 2;
+      }
+      (Some('|'), next) if next != Some('=') => {
+        attribute.namespace = Some(String::new());
+        self.pos += 1;
+      }
+      _ => {}
+    }
+    attribute.name = self.parse_ident();
+    if attribute.namespace.is_none() && self.peek() == Some('|') && self.peek_nth(1) != Some('=') {
+      self.pos += 1;
+      attribute.namespace = Some(std::mem::take(&mut attribute.name));
+      attribute.name = self.parse_ident();
+    }
+    self.skip_whitespace();
+
+    let operator = match (self.peek(), self.peek_nth(1)) {
+      (Some('='), _) => Some((AttributeOperator::Equals, 1)),
+      (Some('~'), Some('=')) => Some((AttributeOperator::Includes, 2)),
+      (Some('|'), Some('=')) => Some((AttributeOperator::DashMatch, 2)),
+      (Some('^'), Some('=')) => Some((AttributeOperator::Prefix, 2)),
+      (Some('$'), Some('=')) => Some((AttributeOperator::Suffix, 2)),
+      (Some('*'), Some('=')) => Some((AttributeOperator::Substring, 2)),
+      _ => None,
+    };
+    if let Some((operator, len)) = operator {
+      self.pos += len;
+      self.skip_whitespace();
+      let mut value = String::new();
+      if matches!(self.peek(), Some('"' | '\'')) {
+        self.parse_string(&mut value);
+      } else {
+        value = self.parse_ident();
+      }
+      attribute.operator = Some(operator);
+      attribute.value = Some(value);
+      self.skip_whitespace();
+      if let Some(modifier) = self.peek().filter(|c| matches!(c, 'i' | 'I' | 's' | 'S'))
+        && self
+          .peek_nth(1)
+          .is_none_or(|c| c == ']' || c.is_whitespace())
+      {
+        attribute.modifier = Some(modifier.to_ascii_lowercase());
+        self.pos += 1;
+      }
+    }
+
+    // anything else up to the closing bracket isn't valid, it is left out.
+    while let Some(c) = self.peek() {
+      match c {
+        ']' => {
+          self.pos += 1;
+          break;
+        }
+        '"' | '\'' => self.parse_string(&mut String::new()),
+        _ => self.pos += 1,
+      }
+    }
+    attribute
+  }
+
+  // arguments of functional pseudo classes, e.g. the `2` in `:nth-child(2)`.
+  fn parse_args(&mut self) -> Option<String> {
+    if self.peek() != Some('(') {
+      return None;
+    }
+    self.pos += 1;
+    let mut depth = 1;
+    let mut args = String::new();
+    while let Some(c) = self.peek() {
+      // parentheses inside of strings don't count, e.g. `:not([title=")"])`.
+      if matches!(c, '"' | '\'') {
+        self.parse_string(&mut args);
+        continue;
+      }
+      self.pos += 1;
+      match c {
+        '(' => depth += 1,
+        ')' => {
+          depth -= 1;
+          if depth == 0 {
+            break;
+          }
+        }
+        _ => {}
+      }
+      args.push(c);
+    }
+    Some(args)
+  }
+}
+
+impl fmt::Display for SelectorList {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, selector) in self.0.iter().enumerate() {
+      if i != 0 {
+        f.write_str(",")?;
+      }
+      write!(f, "{}", selector)?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for Selector {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for compound in self.compounds.iter() {
+      write!(f, "{}", compound)?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for CompoundSelector {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(combinator) = self.combinator {
+      write!(f, "{}", combinator)?;
+    }
+    for selector in self.selectors.iter() {
+      write!(f, "{}", selector)?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for Combinator {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Combinator::Descendant => " ",
+      Combinator::Child => ">",
+      Combinator::NextSibling => "+",
+      Combinator::SubsequentSibling => "~",
+    })
+  }
+}
+
+impl fmt::Display for AttributeSelector {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("[")?;
+    if let Some(namespace) = &self.namespace {
+      write!(f, "{}|", namespace)?;
+    }
+    f.write_str(&self.name)?;
+    if let Some(operator) = self.operator {
+      write!(f, "{}", operator)?;
+    }
+    if let Some(value) = &self.value {
+      f.write_str(value)?;
+    }
+    if let Some(modifier) = self.modifier {
+      write!(f, " {}", modifier)?;
+    }
+    f.write_str("]")
+  }
+}
+
+impl fmt::Display for AttributeOperator {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      AttributeOperator::Equals => "=",
+      AttributeOperator::Includes => "~=",
+      AttributeOperator::DashMatch => "|=",
+      AttributeOperator::Prefix => "^=",
+      AttributeOperator::Suffix => "$=",
+      AttributeOperator::Substring => "*=",
+    })
+  }
+}
+
+impl fmt::Display for SimpleSelector {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SimpleSelector::Type(name) => f.write_str(name),
+      SimpleSelector::Universal => f.write_str("*"),
+      SimpleSelector::Nesting => f.write_str("&"),
+      SimpleSelector::Class(name) => write!(f, ".{}", name),
+      SimpleSelector::Id(name) => write!(f, "#{}", name),
+      SimpleSelector::Attribute(attribute) => write!(f, "{}", attribute),
+      SimpleSelector::PseudoClass { name, args } => {
+        write!(f, ":{}", name)?;
+        match args {
+          Some(args) => write!(f, "({})", args),
+          None => Ok(()),
+        }
+      }
+      SimpleSelector::PseudoElement { name, args } => {
+        write!(f, "::{}", name)?;
+        match args {
+          Some(args) => write!(f, "({})", args),
+          None => Ok(()),
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_structure() {
+    let list = SelectorList::parse("div > p.one:hover, a[target]").unwrap();
+    assert_eq!(list.0.len(), 2);
+    let compounds = &list.0[0].compounds;
+    assert_eq!(compounds[0].combinator, None);
+    assert_eq!(
+      compounds[0].selectors,
+      vec![SimpleSelector::Type("div".into())]
+    );
+    assert_eq!(compounds[1].combinator, Some(Combinator::Child));
+    assert_eq!(
+      compounds[1].selectors,
+      vec![
+        SimpleSelector::Type("p".into()),
+        SimpleSelector::Class("one".into()),
+        SimpleSelector::PseudoClass {
+          name: "hover".into(),
+          args: None
+        },
+      ]
+    );
+    assert_eq!(
+      list.0[1].compounds[0].selectors[1],
+      SimpleSelector::Attribute(AttributeSelector {
+        name: "target".into(),
+        ..Default::default()
+      })
+    );
+  }
+
+  #[test]
+  fn prints_compact() {
+    let list = SelectorList::parse("table  th ,\n table   td ~ p::before").unwrap();
+    assert_eq!(list.to_string(), "table th,table td~p::before");
+  }
+
+  #[test]
+  fn keeps_selector_arguments_together() {
+    let list = SelectorList::parse(":is(h1, h2) span, a:has(> img)").unwrap();
+    assert_eq!(list.0.len(), 2);
+    let [
+      SimpleSelector::PseudoClass {
+        name,
+        args: Some(args),
+      },
+    ] = list.0[0].compounds[0].selectors.as_slice()
+    else {
+      panic!("Expected a single pseudo class");
+    };
+    assert_eq!(
+      SimpleSelector::selector_args(name, args).unwrap().1.0.len(),
+      2
+    );
+    let SimpleSelector::PseudoClass {
+      name,
+      args: Some(args),
+    } = &list.0[1].compounds[0].selectors[1]
+    else {
+      panic!("Expected :has()");
+    };
+    let (_, relative) = SimpleSelector::selector_args(name, args).unwrap();
+    assert_eq!(
+      relative.0[0].compounds[0].combinator,
+      Some(Combinator::Child)
+    );
+  }
+
+  #[test]
+  fn parses_attributes() {
+    let list = SelectorList::parse(r#"[ svg|href $= "a]b" i ],[|id]"#).unwrap();
+    assert_eq!(
+      list.0[0].compounds[0].selectors,
+      vec![SimpleSelector::Attribute(AttributeSelector {
+        namespace: Some("svg".into()),
+        name: "href".into(),
+        operator: Some(AttributeOperator::Suffix),
+        value: Some(r#""a]b""#.into()),
+        modifier: Some('i'),
+      })]
+    );
+    assert_eq!(list.to_string(), r#"[svg|href$="a]b" i],[|id]"#);
+  }
+
+  #[test]
+  fn rejects_invalid_selectors() {
+    let errors: Vec<_> = [
+      "div >",
+      ".a ++ .b",
+      ".a > ~ .b",
+      ".a, ",
+      ", .a",
+      ".a,,.b",
+      "#",
+      ".a .",
+      "p:",
+      "!!!",
+      "div $ p",
+      ".a &-x",
+      "[href]a",
+      ":is(.a >)",
+      ":not(, .a)",
+    ]
+    .into_iter()
+    .map(|selector| {
+      SelectorList::parse(selector)
+        .unwrap_err()
+        .message()
+        .to_string()
+    })
+    .collect();
+    assert_eq!(
+      errors,
+      vec![
+        "Expected a selector after `>`",
+        "Expected a selector after `+`",
+        "Expected a selector after `>`",
+        "Expected a selector after `,`",
+        "Expected a selector before `,`",
+        "Expected a selector before `,`",
+        "Expected a name after `#`",
+        "Expected a name after `.`",
+        "Expected a name after `:`",
+        "Unexpected `!` in the selector",
+        "Unexpected `$` in the selector",
+        "`&-x` isn't valid css nesting, `&` can't be joined with a name",
+        "`a` has to come first in its compound, type selectors can't follow other selectors",
+        "Expected a selector after `>`",
+        "Expected a selector before `,`",
+      ]
+    );
+    // relative selectors and keyframe selectors are fine.
+    assert_eq!(
+      SelectorList::parse("> p, 12.5%, from").unwrap().to_string(),
+      ">p,12.5%,from"
+    );
+  }
+}
@@ -1,78 +1,97 @@
-use proc_macro2::{Delimiter, TokenStream, TokenTree};
-use std::collections::HashSet;
+use proc_macro2::{Span, TokenTree};
+use std::fmt;
 
-use crate::Class;
+use crate::Error;
+use crate::parser::{self, ParseOptions, Severity};
 use crate::style::css_at_rule::AtRule;
 use crate::style::css_style_rule::StyleRule;
+use crate::style::scoping::ScopeStrategy;
+use crate::style::token_source::TokenSource;
 
 // Rule is enum which will have two kinds style-rule and at-rule(which begins with @)
 /// Ressource: <https://developer.mozilla.org/en-US/docs/Web/API/CSSRule>
-#[derive(Debug)]
-pub(crate) enum Rule {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
   StyleRule(StyleRule),
   AtRule(AtRule),
 }
 
 /// Ressources: <https://developer.mozilla.org/en-US/docs/Web/API/StyleSheet> and <https://developer.mozilla.org/en-US/docs/Web/API/CSSStyleSheet>
-#[derive(Debug, Default)]
-pub(crate) struct StyleSheet {
-  pub(crate) rules: Vec<Rule>,
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyleSheet {
+  pub rules: Vec<Rule>,
+  /// Strategy selected with a top level `@scoping root;` rule, which isn't part of the printed css.
+  pub scoping: Option<ScopeStrategy>,
 }
 
 impl StyleSheet {
-  pub(crate) fn new(
+  /// Parses the token stream of a `style!` macro without scoping it.
+  /// is_proc_macro: whether this is called from inside of a procedural macro,
+  /// which is needed to read the spans of the tokens.
+  /// Every invalid part of the css is reported, each at the token it was found at.
+  pub fn new(
     token_stream: impl IntoIterator<Item = TokenTree>,
-    class: &Class,
     is_proc_macro: bool,
-  ) -> (StyleSheet, HashSet<String>) {
-    let mut css_style_sheet = StyleSheet::default();
-
-    let mut css_rule_tt = TokenStream::new();
-    let mut sel_map = HashSet::new();
-
-    let mut is_at_rule = false;
-    let mut count = 0;
+  ) -> Result<StyleSheet, Vec<Error>> {
+    let (style_sheet, errors) = parse_tokens(token_stream, is_proc_macro, Severity::Error);
+    if errors.is_empty() {
+      return Ok(style_sheet);
+    }
+    Err(errors)
+  }
 
-    for tt in token_stream {
-      count += 1;
+  /// Valid css of a `style!` macro which is likely a mistake,
+  /// e.g. `-webkit-transform` without the standard `transform` property.
+  /// Each warning points at the token it was found at.
+  pub fn warnings(
+    token_stream: impl IntoIterator<Item = TokenTree>,
+    is_proc_macro: bool,
+  ) -> Vec<Error> {
+    parse_tokens(token_stream, is_proc_macro, Severity::Warning).1
+  }
+}
 
-      css_rule_tt.extend(Some(tt.clone()));
+// parses the tokens with validation, keeping the reports of the given severity.
+fn parse_tokens(
+  token_stream: impl IntoIterator<Item = TokenTree>,
+  is_proc_macro: bool,
+  severity: Severity,
+) -> (StyleSheet, Vec<Error>) {
+  let source = TokenSource::new(token_stream, is_proc_macro);
+  let (style_sheet, errors) = parser::parse(
+    &source.css,
+    ParseOptions {
+      validate_properties: true,
+      validation_warnings: false,
+    },
+  );
+  let errors = errors
+    .into_iter()
+    .filter(|error| error.severity == severity)
+    .map(|error| {
+      let span = source
+        .span_at(error.range.start)
+        .unwrap_or_else(Span::call_site);
+      Error::new(error.message, span)
+    })
+    .collect();
+  (style_sheet, errors)
+}
 
-      match tt {
-        TokenTree::Group(t) => {
-          if t.delimiter() == Delimiter::Brace {
-            count = 0;
-            if is_at_rule {
-              let (at_rule, new_map) = AtRule::new(css_rule_tt, class, is_proc_macro);
-              css_style_sheet.rules.push(Rule::AtRule(at_rule));
-              sel_map.extend(new_map);
-              is_at_rule = false;
-            } else {
-              let (style_rule, new_map) = StyleRule::new(css_rule_tt, class, is_proc_macro);
-              css_style_sheet.rules.push(Rule::StyleRule(style_rule));
-              sel_map.extend(new_map);
-            }
-            css_rule_tt = TokenStream::new();
-          }
-        }
-        TokenTree::Punct(p) => {
-          let ch = p.as_char();
-          if ch == '@' && count == 1 {
-            is_at_rule = true;
-          }
-          //in regular at-rule css rule ends with semicolon without any style declaration.
-          if is_at_rule && ch == ';' {
-            let (at_rule, new_map) = AtRule::new(css_rule_tt, class, is_proc_macro);
-            css_style_sheet.rules.push(Rule::AtRule(at_rule));
-            sel_map.extend(new_map);
-            is_at_rule = false;
-            css_rule_tt = TokenStream::new();
-          }
-        }
-        _ => continue,
-      }
+impl fmt::Display for Rule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Rule::StyleRule(style_rule) => write!(f, "{}", style_rule),
+      Rule::AtRule(at_rule) => write!(f, "{}", at_rule),
     }
+  }
+}
 
-    (css_style_sheet, sel_map)
+impl fmt::Display for StyleSheet {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for rule in self.rules.iter() {
+      write!(f, "{}", rule)?;
+    }
+    Ok(())
   }
 }
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use crate::Class;
+use crate::style::css_style_declar::for_each_declaration;
+use crate::style::{AtRuleBlock, Rule, StyleSheet};
+
+impl StyleSheet {
+  /// Suffixes the custom properties declared in the style sheet with the class, e.g. `--gap` becomes `--gap-l-xxxxxx`,
+  /// and renames them wherever they are used in the same style sheet, e.g. in `var(--gap)`.
+  /// Properties starting with `public_prefix` are left alone so that they can be set from outside, e.g. for theming.
+  pub(crate) fn scope_custom_properties(&mut self, class: &Class, public_prefix: Option<&str>) {
+    let mut names = HashMap::new();
+    let mut scope_name = |name: &str| {
+      if name.starts_with("--") && !public_prefix.is_some_and(|prefix| name.starts_with(prefix)) {
+        names.insert(name.to_string(), format!("{}-{}", name, class.as_name()));
+      }
+    };
+    for_each_declaration(&mut self.rules, &mut |declaration| {
+      scope_name(&declaration.property)
+    });
+    registered_properties(&self.rules, &mut scope_name);
+    if names.is_empty() {
+      return;
+    }
+
+    for_each_declaration(&mut self.rules, &mut |declaration| {
+      if let Some(name) = names.get(&declaration.property) {
+        declaration.property = name.clone();
+      }
+      declaration.rename_idents(&names);
+    });
+    rename_registered_properties(&mut self.rules, &names);
+  }
+}
+
+// names registered with `@property --name {...}`.
+fn registered_properties(rules: &[Rule], f: &mut impl FnMut(&str)) {
+  for rule in rules.iter() {
+    if let Rule::AtRule(at_rule) = rule {
+      match &at_rule.block {
+        _ if at_rule.name == "property" => f(&at_rule.prelude),
+        Some(AtRuleBlock::Rules(rules)) => registered_properties(rules, f),
+        _ => {}
+      }
+    }
+  }
+}
+
+fn rename_registered_properties(rules: &mut [Rule], names: &HashMap<String, String>) {
+  for rule in rules.iter_mut() {
+    if let Rule::AtRule(at_rule) = rule {
+      match &mut at_rule.block {
+        _ if at_rule.name == "property" => {
+          if let Some(name) = names.get(&at_rule.prelude) {
+            at_rule.prelude = name.clone();
+          }
+        }
+        Some(AtRuleBlock::Rules(rules)) => rename_registered_properties(rules, names),
+        _ => {}
+      }
+    }
+  }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use crate::Class;
+use crate::parser::tokenizer::{TokenKind, tokenize};
+use crate::style::css_selector::{SelectorList, SimpleSelector};
+use crate::style::css_style_declar::for_each_declaration;
+use crate::style::{AtRuleBlock, Rule, StyleSheet};
+
+impl StyleSheet {
+  /// Suffixes the names of the `@keyframes` of the style sheet with the class, e.g. `spin` becomes `spin-l-xxxxxx`,
+  /// and renames them in the `animation` and `animation-name` declarations of the same style sheet.
+  /// Keyframes shared with other components opt out with `@keyframes :global(spin)`.
+  pub(crate) fn scope_keyframes(&mut self, class: &Class) {
+    let mut names = HashMap::new();
+    rename_keyframes(&mut self.rules, class, &mut names);
+    if names.is_empty() {
+      return;
+    }
+    for_each_declaration(&mut self.rules, &mut |declaration| {
+      // vendor prefixed properties too, e.g. `-webkit-animation`.
+      let property = declaration.property.to_ascii_lowercase();
+      let property = match property.strip_prefix('-') {
+        Some(prefixed) if !prefixed.starts_with('-') => prefixed
+          .split_once('-')
+          .map_or("", |(_, property)| property),
+        _ => &property,
+      };
+      if matches!(property, "animation" | "animation-name") {
+        declaration.rename_idents(&names);
+      }
+    });
+  }
+}
+
+fn rename_keyframes(rules: &mut [Rule], class: &Class, names: &mut HashMap<String, String>) {
+  for rule in rules.iter_mut() {
+    let Rule::AtRule(at_rule) = rule else {
+      continue;
+    };
+    if !at_rule.name.ends_with("keyframes") {
+      if let Some(AtRuleBlock::Rules(rules)) = &mut at_rule.block {
+        rename_keyframes(rules, class, names);
+      }
+      continue;
+    }
+    if let Some(global) = global_name(&at_rule.prelude) {
+      at_rule.prelude = global;
+    } else if tokenize(&at_rule.prelude)
+      .iter()
+      .all(|token| token.kind == TokenKind::Ident)
+      && !at_rule.prelude.is_empty()
+    {
+      let scoped = format!("{}-{}", at_rule.prelude, class.as_name());
+      names.insert(at_rule.prelude.clone(), scoped.clone());
+      at_rule.prelude = scoped;
+    }
+  }
+}
+
+// name inside of `:global(spin)`.
+fn global_name(prelude: &str) -> Option<String> {
+  let list = SelectorList::parse(prelude).ok()?;
+  let [selector] = list.0.as_slice() else {
+    return None;
+  };
+  let [compound] = selector.compounds.as_slice() else {
+    return None;
+  };
+  match compound.selectors.as_slice() {
+    [
+      SimpleSelector::PseudoClass {
+        name,
+        args: Some(args),
+      },
+    ] if name == "global" => Some(args.trim().to_string()),
+    _ => None,
+  }
+}
@@ -0,0 +1,582 @@
+use std::mem;
+
+use crate::parser::tokenizer::{TokenKind, tokenize};
+use crate::style::values::{LENGTH_UNITS, names_colors};
+use crate::style::{AtRuleBlock, Declaration, Rule, StyleDeclaration, StyleSheet};
+
+impl StyleSheet {
+  /// Shrinks the style sheet without changing what it does: whitespace inside of values is removed where it isn't needed,
+  /// colors get their shortest form, e.g. `#FFFFFF` becomes `#fff`, `#ff0000` becomes `red` and `#aabbccdd` becomes `#abcd`,
+  /// lengths of zero lose their unit, adjacent rules with identical selectors are merged
+  /// and a declaration is removed when the same property is declared later in the block with the same value.
+  /// Values of custom properties are left as they are. Print the result with [`StyleSheet::to_minified_string`].
+  pub fn minify(&mut self) {
+    minify_rules(&mut self.rules);
+  }
+
+  /// Prints the css without any optional whitespace or semicolon, e.g. `a{color:red;margin:0}`.
+  pub fn to_minified_string(&self) -> String {
+    let mut css = String::new();
+    write_rules(&self.rules, &mut css);
+    css
+  }
+}
+
+fn minify_rules(rules: &mut Vec<Rule>) {
+  for rule in rules.iter_mut() {
+    match rule {
+      Rule::StyleRule(style_rule) => minify_declarations(&mut style_rule.style),
+      Rule::AtRule(at_rule) => match &mut at_rule.block {
+        Some(AtRuleBlock::Rules(rules)) => minify_rules(rules),
+        Some(AtRuleBlock::Declarations(style)) => minify_declarations(style),
+        None => {}
+      },
+    }
+  }
+  rules.retain(|rule| {
+    !matches!(rule, Rule::StyleRule(style_rule)
+      if style_rule.style.declarations.is_empty() && style_rule.style.rules.is_empty())
+  });
+
+  let mut merged: Vec<Rule> = Vec::with_capacity(rules.len());
+  for rule in mem::take(rules) {
+    if let (Some(Rule::StyleRule(previous)), Rule::StyleRule(style_rule)) =
+      (merged.last_mut(), &rule)
+      && previous.selectors == style_rule.selectors
+      && previous.style.rules.is_empty()
+      && style_rule.style.rules.is_empty()
+    {
+      previous
+        .style
+        .declarations
+        .extend(style_rule.style.declarations.iter().cloned());
+      remove_duplicates(&mut previous.style.declarations);
+      continue;
+    }
+    merged.push(rule);
+  }
+  *rules = merged;
+}
+
+fn minify_declarations(style: &mut StyleDeclaration) {
+  for declaration in style.declarations.iter_mut() {
+    declaration.value = minify_value(&declaration.property, &declaration.value);
+  }
+  remove_duplicates(&mut style.declarations);
+  minify_rules(&mut style.rules);
+}
+
+// removes declarations repeated later on in the block, which override them anyway.
+// Different values of the same property are kept, since they are often fallbacks, e.g. `display: -webkit-box;display: flex;`.
+fn remove_duplicates(declarations: &mut Vec<Declaration>) {
+  let mut i = 0;
+  while i < declarations.len() {
+    let declaration = &declarations[i];
+    let overridden = declarations[i + 1..].iter().any(|later| {
+      later.property == declaration.property
+        && later.value == declaration.value
+        && (later.important || !declaration.important)
+    });
+    if overridden {
+      declarations.remove(i);
+    } else {
+      i += 1;
+    }
+  }
+}
+
+fn minify_value(property: &str, value: &str) -> String {
+  if property.starts_with("--") {
+    return value.to_string();
+  }
+  let property = property.to_ascii_lowercase();
+  let names_colors = names_colors(&property);
+  // a unitless zero of `flex` could be read as its grow or shrink factor.
+  let strips_zero_units = property != "flex";
+  let tokens = tokenize(value);
+  let kind = |i: Option<usize>| i.and_then(|i| tokens.get(i)).map(|token| token.kind);
+  let mut minified = String::with_capacity(value.len());
+  // nesting of functions and parentheses, lengths inside of them keep their unit, e.g. `calc(0px + 1em)`.
+  let mut depth = 0usize;
+  let mut i = 0;
+  while i < tokens.len() {
+    let token = &tokens[i];
+    let text = &value[token.range.clone()];
+    match token.kind {
+      TokenKind::Whitespace => {
+        let (previous, next) = (kind(i.checked_sub(1)), kind(Some(i + 1)));
+        let needed = previous.is_some_and(|previous| {
+          !matches!(
+            previous,
+            TokenKind::Comma | TokenKind::OpenParen | TokenKind::Function | TokenKind::Delim('/')
+          )
+        }) && next.is_some_and(|next| {
+          !matches!(
+            next,
+            TokenKind::Comma | TokenKind::CloseParen | TokenKind::Delim('/')
+          )
+        });
+        if needed {
+          minified.push(' ');
+        }
+      }
+      TokenKind::Function => {
+        let name = text.trim_end_matches('(').to_ascii_lowercase();
+        if matches!(name.as_str(), "rgb" | "rgba")
+          && let Some(end) = (i..tokens.len()).find(|&j| tokens[j].kind == TokenKind::CloseParen)
+          && let Some(color) = rgb_color(&value[tokens[i].range.end..tokens[end].range.start])
+        {
+          minified.push_str(&color);
+          i = end + 1;
+          continue;
+        }
+        depth += 1;
+        minified.push_str(text);
+      }
+      TokenKind::OpenParen => {
+        depth += 1;
+        minified.push_str(text);
+      }
+      TokenKind::CloseParen => {
+        depth = depth.saturating_sub(1);
+        minified.push_str(text);
+      }
+      TokenKind::Hash => match (hex_value(&text[1..]), translucent_hex(&text[1..])) {
+        (Some(rgb), _) => minified.push_str(&shortest_color(rgb, text)),
+        (None, Some(hex)) => minified.push_str(&hex),
+        (None, None) => minified.push_str(text),
+      },
+      TokenKind::Ident if names_colors => match named_color(text) {
+        Some(rgb) => minified.push_str(&shortest_color(rgb, text)),
+        None => minified.push_str(text),
+      },
+      TokenKind::Number => minified.push_str(&shorten_number(text)),
+      TokenKind::Percentage => {
+        minified.push_str(&shorten_number(&text[..text.len() - 1]));
+        minified.push('%');
+      }
+      TokenKind::Dimension => match split_dimension(text) {
+        Some((number, unit)) => {
+          let number = shorten_number(number);
+          minified.push_str(&number);
+          let is_zero = number.trim_start_matches(['+', '-']) == "0";
+          if !(is_zero
+            && depth == 0
+            && strips_zero_units
+            && LENGTH_UNITS.contains(&unit.to_ascii_lowercase().as_str()))
+          {
+            minified.push_str(unit);
+          }
+        }
+        None => minified.push_str(text),
+      },
+      _ => minified.push_str(text),
+    }
+    i += 1;
+  }
+  minified
+}
+
+// `1.50` becomes `1.5` and `0.5` becomes `.5`.
+fn shorten_number(number: &str) -> String {
+  if number.contains(['e', 'E']) {
+    return number.to_string();
+  }
+  let (sign, digits) = match number.strip_prefix(['+', '-']) {
+    Some(digits) => (&number[..1], digits),
+    None => ("", number),
+  };
+  let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+  let fraction = fraction.trim_end_matches('0');
+  let integer = integer.trim_start_matches('0');
+  match (integer, fraction) {
+    ("", "") => format!("{}0", sign),
+    (integer, "") => format!("{}{}", sign, integer),
+    (integer, fraction) => format!("{}{}.{}", sign, integer, fraction),
+  }
+}
+
+// number and unit of a dimension, unless the number has an exponent, e.g. `1e3px`.
+fn split_dimension(dimension: &str) -> Option<(&str, &str)> {
+  let start = dimension
+    .char_indices()
+    .find(|(i, c)| !(c.is_ascii_digit() || *c == '.' || (*i == 0 && matches!(c, '+' | '-'))))?
+    .0;
+  let (number, unit) = dimension.split_at(start);
+  let mut unit_chars = unit.chars();
+  if matches!(unit_chars.next(), Some('e' | 'E'))
+    && matches!(unit_chars.next(), Some(c) if c.is_ascii_digit() || c == '+' || c == '-')
+  {
+    return None;
+  }
+  Some((number, unit))
+}
+
+// opaque color of an `rgb()` with integer channels, e.g. `255, 0, 0` or `255 0 0 / 1`.
+fn rgb_color(arguments: &str) -> Option<String> {
+  let tokens = tokenize(arguments);
+  let components = tokens
+    .iter()
+    .filter(|token| {
+      !matches!(
+        token.kind,
+        TokenKind::Whitespace | TokenKind::Comma | TokenKind::Delim('/')
+      )
+    })
+    .map(|token| (token.kind, &arguments[token.range.clone()]))
+    .collect::<Vec<_>>();
+  let (channels, alpha) = components.split_at_checked(3)?;
+  match alpha {
+    [] | [(TokenKind::Number, "1")] | [(TokenKind::Percentage, "100%")] => {}
+    _ => return None,
+  }
+  let mut rgb = 0;
+  for (kind, text) in channels {
+    let channel = text
+      .parse::<u8>()
+      .ok()
+      .filter(|_| *kind == TokenKind::Number)?;
+    rgb = rgb << 8 | u32::from(channel);
+  }
+  Some(shortest_color(rgb, ""))
+}
+
+// opaque color of 3 or 6 hex digits, or of 4 or 8 with an alpha of `f` or `ff`.
+fn hex_value(hex: &str) -> Option<u32> {
+  if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+    return None;
+  }
+  match hex.len() {
+    3 => {
+      let expanded = hex.chars().flat_map(|c| [c, c]).collect::<String>();
+      u32::from_str_radix(&expanded, 16).ok()
+    }
+    6 => u32::from_str_radix(hex, 16).ok(),
+    4 if hex[3..].eq_ignore_ascii_case("f") => hex_value(&hex[..3]),
+    8 if hex[6..].eq_ignore_ascii_case("ff") => hex_value(&hex[..6]),
+    _ => None,
+  }
+}
+
+// shortest form of a color of 4 or 8 hex digits which isn't opaque, e.g. `#AABBCC80` becomes `#aabbcc80`
+// and `#aabbccdd` becomes `#abcd`.
+fn translucent_hex(hex: &str) -> Option<String> {
+  if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+    return None;
+  }
+  let hex = hex.to_ascii_lowercase();
+  let bytes = hex.as_bytes();
+  match hex.len() {
+    4 => Some(format!("#{}", hex)),
+    8 if bytes.chunks(2).all(|pair| pair[0] == pair[1]) => {
+      Some(format!("#{}", hex.chars().step_by(2).collect::<String>()))
+    }
+    8 => Some(format!("#{}", hex)),
+    _ => None,
+  }
+}
+
+fn named_color(name: &str) -> Option<u32> {
+  let name = name.to_ascii_lowercase();
+  NAMED_COLORS
+    .iter()
+    .find(|(color, _)| *color == name)
+    .map(|(_, rgb)| *rgb)
+}
+
+// shortest of the lowercase hex forms and names of the color, a `written` name is kept unless something is shorter.
+fn shortest_color(rgb: u32, written: &str) -> String {
+  let hex = format!("{:06x}", rgb);
+  let bytes = hex.as_bytes();
+  let mut shortest = if bytes[0] == bytes[1] && bytes[2] == bytes[3] && bytes[4] == bytes[5] {
+    format!("#{}{}{}", &hex[0..1], &hex[2..3], &hex[4..5])
+  } else {
+    format!("#{}", hex)
+  };
+  if let Some((name, _)) = NAMED_COLORS
+    .iter()
+    .filter(|(_, color)| *color == rgb)
+    .min_by_key(|(name, _)| name.len())
+    && name.len() < shortest.len()
+  {
+    shortest = name.to_string();
+  }
+  if !written.is_empty()
+    && (written.len() < shortest.len()
+      || written.len() == shortest.len() && !written.starts_with('#'))
+  {
+    return written.to_ascii_lowercase();
+  }
+  shortest
+}
+
+fn write_rules(rules: &[Rule], css: &mut String) {
+  for rule in rules.iter() {
+    match rule {
+      Rule::StyleRule(style_rule) => {
+        css.push_str(&style_rule.selectors.to_string());
+        write_declarations(&style_rule.style, css);
+      }
+      Rule::AtRule(at_rule) => {
+        css.push('@');
+        css.push_str(&at_rule.name);
+        if !at_rule.prelude.is_empty() {
+          css.push(' ');
+          css.push_str(&minify_prelude(&at_rule.prelude));
+        }
+        match &at_rule.block {
+          None => css.push(';'),
+          Some(AtRuleBlock::Declarations(style)) => write_declarations(style, css),
+          Some(AtRuleBlock::Rules(rules)) => {
+            css.push('{');
+            write_rules(rules, css);
+            css.push('}');
+          }
+        }
+      }
+    }
+  }
+}
+
+fn write_declarations(style: &StyleDeclaration, css: &mut String) {
+  css.push('{');
+  for (i, declaration) in style.declarations.iter().enumerate() {
+    if i != 0 {
+      css.push(';');
+    }
+    css.push_str(&declaration.property);
+    css.push(':');
+    css.push_str(&declaration.value);
+    if declaration.important {
+      css.push_str("!important");
+    }
+  }
+  // nested rules would be read as part of the last value otherwise.
+  if !style.declarations.is_empty() && !style.rules.is_empty() {
+    css.push(';');
+  }
+  write_rules(&style.rules, css);
+  css.push('}');
+}
+
+// `screen and (min-width: 900px)` becomes `screen and (min-width:900px)`.
+// The space before a parenthesis stays, `and(` would be a function.
+fn minify_prelude(prelude: &str) -> String {
+  let tokens = tokenize(prelude);
+  let mut minified = String::with_capacity(prelude.len());
+  for (i, token) in tokens.iter().enumerate() {
+    if token.kind == TokenKind::Whitespace {
+      let previous = i.checked_sub(1).map(|i| tokens[i].kind);
+      let next = tokens.get(i + 1).map(|token| token.kind);
+      if matches!(
+        previous,
+        Some(TokenKind::Colon | TokenKind::Comma | TokenKind::OpenParen)
+      ) || matches!(next, Some(TokenKind::Comma | TokenKind::CloseParen))
+      {
+        continue;
+      }
+    }
+    minified.push_str(&prelude[token.range.clone()]);
+  }
+  minified
+}
+
+/// ref: <https://www.w3.org/TR/css-color-4/#named-colors>
+const NAMED_COLORS: &[(&str, u32)] = &[
+  ("aliceblue", 0xf0f8ff),
+  ("antiquewhite", 0xfaebd7),
+  ("aqua", 0x00ffff),
+  ("aquamarine", 0x7fffd4),
+  ("azure", 0xf0ffff),
+  ("beige", 0xf5f5dc),
+  ("bisque", 0xffe4c4),
+  ("black", 0x000000),
+  ("blanchedalmond", 0xffebcd),
+  ("blue", 0x0000ff),
+  ("blueviolet", 0x8a2be2),
+  ("brown", 0xa52a2a),
+  ("burlywood", 0xdeb887),
+  ("cadetblue", 0x5f9ea0),
+  ("chartreuse", 0x7fff00),
+  ("chocolate", 0xd2691e),
+  ("coral", 0xff7f50),
+  ("cornflowerblue", 0x6495ed),
+  ("cornsilk", 0xfff8dc),
+  ("crimson", 0xdc143c),
+  ("cyan", 0x00ffff),
+  ("darkblue", 0x00008b),
+  ("darkcyan", 0x008b8b),
+  ("darkgoldenrod", 0xb8860b),
+  ("darkgray", 0xa9a9a9),
+  ("darkgreen", 0x006400),
+  ("darkgrey", 0xa9a9a9),
+  ("darkkhaki", 0xbdb76b),
+  ("darkmagenta", 0x8b008b),
+  ("darkolivegreen", 0x556b2f),
+  ("darkorange", 0xff8c00),
+  ("darkorchid", 0x9932cc),
+  ("darkred", 0x8b0000),
+  ("darksalmon", 0xe9967a),
+  ("darkseagreen", 0x8fbc8f),
+  ("darkslateblue", 0x483d8b),
+  ("darkslategray", 0x2f4f4f),
+  ("darkslategrey", 0x2f4f4f),
+  ("darkturquoise", 0x00ced1),
+  ("darkviolet", 0x9400d3),
+  ("deeppink", 0xff1493),
+  ("deepskyblue", 0x00bfff),
+  ("dimgray", 0x696969),
+  ("dimgrey", 0x696969),
+  ("dodgerblue", 0x1e90ff),
+  ("firebrick", 0xb22222),
+  ("floralwhite", 0xfffaf0),
+  ("forestgreen", 0x228b22),
+  ("fuchsia", 0xff00ff),
+  ("gainsboro", 0xdcdcdc),
+  ("ghostwhite", 0xf8f8ff),
+  ("gold", 0xffd700),
+  ("goldenrod", 0xdaa520),
+  ("gray", 0x808080),
+  ("green", 0x008000),
+  ("greenyellow", 0xadff2f),
+  ("grey", 0x808080),
+  ("honeydew", 0xf0fff0),
+  ("hotpink", 0xff69b4),
+  ("indianred", 0xcd5c5c),
+  ("indigo", 0x4b0082),
+  ("ivory", 0xfffff0),
+  ("khaki", 0xf0e68c),
+  ("lavender", 0xe6e6fa),
+  ("lavenderblush", 0xfff0f5),
+  ("lawngreen", 0x7cfc00),
+  ("lemonchiffon", 0xfffacd),
+  ("lightblue", 0xadd8e6),
+  ("lightcoral", 0xf08080),
+  ("lightcyan", 0xe0ffff),
+  ("lightgoldenrodyellow", 0xfafad2),
+  ("lightgray", 0xd3d3d3),
+  ("lightgreen", 0x90ee90),
+  ("lightgrey", 0xd3d3d3),
+  ("lightpink", 0xffb6c1),
+  ("lightsalmon", 0xffa07a),
+  ("lightseagreen", 0x20b2aa),
+  ("lightskyblue", 0x87cefa),
+  ("lightslategray", 0x778899),
+  ("lightslategrey", 0x778899),
+  ("lightsteelblue", 0xb0c4de),
+  ("lightyellow", 0xffffe0),
+  ("lime", 0x00ff00),
+  ("limegreen", 0x32cd32),
+  ("linen", 0xfaf0e6),
+  ("magenta", 0xff00ff),
+  ("maroon", 0x800000),
+  ("mediumaquamarine", 0x66cdaa),
+  ("mediumblue", 0x0000cd),
+  ("mediumorchid", 0xba55d3),
+  ("mediumpurple", 0x9370db),
+  ("mediumseagreen", 0x3cb371),
+  ("mediumslateblue", 0x7b68ee),
+  ("mediumspringgreen", 0x00fa9a),
+  ("mediumturquoise", 0x48d1cc),
+  ("mediumvioletred", 0xc71585),
+  ("midnightblue", 0x191970),
+  ("mintcream", 0xf5fffa),
+  ("mistyrose", 0xffe4e1),
+  ("moccasin", 0xffe4b5),
+  ("navajowhite", 0xffdead),
+  ("navy", 0x000080),
+  ("oldlace", 0xfdf5e6),
+  ("olive", 0x808000),
+  ("olivedrab", 0x6b8e23),
+  ("orange", 0xffa500),
+  ("orangered", 0xff4500),
+  ("orchid", 0xda70d6),
+  ("palegoldenrod", 0xeee8aa),
+  ("palegreen", 0x98fb98),
+  ("paleturquoise", 0xafeeee),
+  ("palevioletred", 0xdb7093),
+  ("papayawhip", 0xffefd5),
+  ("peachpuff", 0xffdab9),
+  ("peru", 0xcd853f),
+  ("pink", 0xffc0cb),
+  ("plum", 0xdda0dd),
+  ("powderblue", 0xb0e0e6),
+  ("purple", 0x800080),
+  ("rebeccapurple", 0x663399),
+  ("red", 0xff0000),
+  ("rosybrown", 0xbc8f8f),
+  ("royalblue", 0x4169e1),
+  ("saddlebrown", 0x8b4513),
+  ("salmon", 0xfa8072),
+  ("sandybrown", 0xf4a460),
+  ("seagreen", 0x2e8b57),
+  ("seashell", 0xfff5ee),
+  ("sienna", 0xa0522d),
+  ("silver", 0xc0c0c0),
+  ("skyblue", 0x87ceeb),
+  ("slateblue", 0x6a5acd),
+  ("slategray", 0x708090),
+  ("slategrey", 0x708090),
+  ("snow", 0xfffafa),
+  ("springgreen", 0x00ff7f),
+  ("steelblue", 0x4682b4),
+  ("tan", 0xd2b48c),
+  ("teal", 0x008080),
+  ("thistle", 0xd8bfd8),
+  ("tomato", 0xff6347),
+  ("turquoise", 0x40e0d0),
+  ("violet", 0xee82ee),
+  ("wheat", 0xf5deb3),
+  ("white", 0xffffff),
+  ("whitesmoke", 0xf5f5f5),
+  ("yellow", 0xffff00),
+  ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn minified(css: &str) -> String {
+    let mut style_sheet = css.parse::<StyleSheet>().unwrap();
+    style_sheet.minify();
+    style_sheet.to_minified_string()
+  }
+
+  #[test]
+  fn minifies_values() {
+    assert_eq!(
+      minified(
+        "a { color: #FFFFFF; background: rgb(255, 0, 0) url( \"x.png\" ); border-color: white #ff0000 #aabbcc; }"
+      ),
+      "a{color:#fff;background:red url(\"x.png\");border-color:#fff red #abc}"
+    );
+    assert_eq!(
+      minified(
+        "a { color: #FFF; background: #AABBCCDD; border-color: #ff0000ff #aBc8 #12345678; }"
+      ),
+      "a{color:#fff;background:#abcd;border-color:red #abc8 #12345678}"
+    );
+    assert_eq!(
+      minified(
+        "a { margin: 0px 0.50em 1.0px; width: calc(0px + 10%); font: 12px / 1.5 a, b; opacity: 0.5 !important; }"
+      ),
+      "a{margin:0 .5em 1px;width:calc(0px + 10%);font:12px/1.5 a,b;opacity:.5!important}"
+    );
+    // names aren't colors everywhere, and custom properties are kept.
+    assert_eq!(
+      minified("a { grid-area: white; --gap: 0px , 1px; transition: 0s; flex: 1 1 0px; }"),
+      "a{grid-area:white;--gap:0px , 1px;transition:0s;flex:1 1 0px}"
+    );
+  }
+
+  #[test]
+  fn merges_rules() {
+    assert_eq!(
+      minified(
+        "a { color: red; } a { margin: 0; color: red; } b {} a { color: blue; }\n@media screen and (min-width: 900px) { a { display: -webkit-box; display: flex; display: flex; } }"
+      ),
+      "a{margin:0;color:red;color:blue}@media screen and (min-width:900px){a{display:-webkit-box;display:flex}}"
+    );
+  }
+}
@@ -2,42 +2,81 @@
 //! The main focus of this function is to provide scoped css for Rust components(for the framework which provides component like architecture e.g leptos).
 //! This function can be used parse the style sheet in rust.
 mod css_at_rule;
-mod css_style_declar;
+mod css_module;
+mod css_selector;
+pub(crate) mod css_style_declar;
 mod css_style_rule;
 mod css_style_sheet;
-mod utils;
+mod custom_properties;
+mod keyframes;
+mod minify;
+mod nesting;
+mod prefixing;
+mod pretty;
+pub(crate) mod properties;
+mod scoping;
+mod token_source;
+pub(crate) mod values;
+use std::collections::BTreeMap;
+
 use proc_macro2::TokenTree;
-use std::collections::HashSet;
 
-use crate::Class;
-pub(crate) use crate::style::css_at_rule::AtRule;
-pub(crate) use crate::style::css_style_declar::StyleDeclaration;
-pub(crate) use crate::style::css_style_rule::StyleRule;
-pub(crate) use crate::style::css_style_sheet::{Rule, StyleSheet};
+pub use crate::style::css_at_rule::{AtRule, AtRuleBlock};
+pub use crate::style::css_selector::{
+  AttributeOperator, AttributeSelector, Combinator, CompoundSelector, Selector, SelectorList,
+  SimpleSelector,
+};
+pub use crate::style::css_style_declar::{Declaration, StyleDeclaration};
+pub use crate::style::css_style_rule::StyleRule;
+pub use crate::style::css_style_sheet::{Rule, StyleSheet};
+pub use crate::style::scoping::{ScopeOptions, ScopeStrategy, SelectorMap};
+use crate::{Class, Error};
 
 /// This function will build the whole style text as rust TokenStream.
 /// This function will take two arguments.
 /// ts: TokenStream which is token stream of text content of whole style sheet.
 /// random_class: &String is random class to be appended for each selector.
-/// This function will return tuple with two fields (style string, map of unique keys of selectors.)
+/// options: how the class is applied, e.g. which compound selectors get it.
+/// This function will return tuple with two fields (style string, [`SelectorMap`] of what the scoped selectors refer to.)
 /// style string: is the parsed style sheet as a string
+/// Invalid css is returned as errors pointing at the offending tokens instead.
 pub fn build_style_from_ts(
   token_stream: impl Iterator<Item = TokenTree>,
   class: &Class,
+  options: &ScopeOptions,
   is_proc_macro: bool,
-) -> (String, HashSet<String>) {
-  let mut style = String::new();
-
-  let (style_sheet, sel_map) = StyleSheet::new(token_stream, class, is_proc_macro);
+) -> Result<(String, SelectorMap), Vec<Error>> {
+  let mut style_sheet = StyleSheet::new(token_stream, is_proc_macro)?;
+  let sel_map = style_sheet.scope(class, options);
 
   tracing::trace!(?style_sheet, ?sel_map);
 
-  style_sheet.rules.iter().for_each(|rule| match rule {
-    Rule::AtRule(at_rule) => style.push_str(&at_rule.css_text()),
-    Rule::StyleRule(style_rule) => style.push_str(&style_rule.css_text()),
-  });
+  Ok((style_sheet.to_string(), sel_map))
+}
+
+/// Same as [`build_style_from_ts`] but scopes the style sheet as a css module, see [`StyleSheet::scope_module`].
+/// Returns the style string with the suffixed name of every local class name.
+pub fn build_module_from_ts(
+  token_stream: impl Iterator<Item = TokenTree>,
+  class: &Class,
+  options: &ScopeOptions,
+  is_proc_macro: bool,
+) -> Result<(String, BTreeMap<String, String>), Vec<Error>> {
+  let mut style_sheet = StyleSheet::new(token_stream, is_proc_macro)?;
+  let names = style_sheet.scope_module(class, options);
 
-  (style, sel_map)
+  tracing::trace!(?style_sheet, ?names);
+
+  Ok((style_sheet.to_string(), names))
+}
+
+/// Warnings about valid css of the token stream which is likely a mistake, see [`StyleSheet::warnings`].
+/// Invalid css is reported by [`build_style_from_ts`] instead.
+pub fn build_warnings_from_ts(
+  token_stream: impl Iterator<Item = TokenTree>,
+  is_proc_macro: bool,
+) -> Vec<Error> {
+  StyleSheet::warnings(token_stream, is_proc_macro)
 }
 
 #[cfg(test)]
@@ -59,10 +98,68 @@ mod tests {
     };
 
     let class = Class::new("test".into());
-    let (style, _) = build_style_from_ts(input.into_iter(), &class, true);
+    let (style, _) =
+      build_style_from_ts(input.into_iter(), &class, &ScopeOptions::default(), true).unwrap();
     assert_eq!(
       style,
       "div.test {border: 1px solid black;margin: 25px 50px 75px 100px;background-color: lightblue;}"
     );
   }
+
+  #[test]
+  fn reports_errors_at_their_tokens() {
+    let input: proc_macro2::TokenStream = "p { colr: red; }\ndiv {\n  margin: 0\n  padding: 0;\n}"
+      .parse()
+      .unwrap();
+    let errors = StyleSheet::new(input, false).unwrap_err();
+    let errors: Vec<_> = errors
+      .iter()
+      .map(|error| {
+        let start = error.span().start();
+        (error.message(), start.line, start.column)
+      })
+      .collect();
+    assert_eq!(
+      errors,
+      vec![
+        ("Did you mean to use color property", 1, 4),
+        ("Missing semicolon before `padding`", 4, 2),
+      ]
+    );
+  }
+
+  #[test]
+  fn warns_about_prefixed_properties() {
+    let input: proc_macro2::TokenStream = "p { -webkit-transform: none; -moz-appearance: none; appearance: none; }\ndiv { -webkit-box-orient: vertical; -ms-overflow-style: none; }"
+      .parse()
+      .unwrap();
+    assert!(StyleSheet::new(input.clone(), false).is_ok());
+    let warnings: Vec<_> = StyleSheet::warnings(input, false)
+      .iter()
+      .map(|warning| (warning.message().to_string(), warning.span().start().line))
+      .collect();
+    assert_eq!(
+      warnings,
+      vec![(
+        "`-webkit-transform` is used without the standard `transform` property".to_string(),
+        1
+      )]
+    );
+
+    let input: proc_macro2::TokenStream = "p { -o-color: red; -webkit-apperance: none; }"
+      .parse()
+      .unwrap();
+    let errors: Vec<_> = StyleSheet::new(input, false)
+      .unwrap_err()
+      .iter()
+      .map(|error| error.message().to_string())
+      .collect();
+    assert_eq!(
+      errors,
+      vec![
+        "Did you mean to use color property",
+        "Did you mean to use -webkit-appearance property",
+      ]
+    );
+  }
 }
@@ -0,0 +1,232 @@
+use std::mem;
+
+use crate::style::css_selector::{
+  Combinator, CompoundSelector, Selector, SelectorList, SimpleSelector,
+};
+use crate::style::{AtRule, AtRuleBlock, Rule, StyleRule, StyleSheet};
+
+impl StyleSheet {
+  /// Replaces nested style rules by top level rules with their selectors resolved against the parent rule,
+  /// e.g. `.a{color: red;&:hover{color: blue;}}` becomes `.a{color: red;}.a:hover{color: blue;}`.
+  /// At-rules like `@media` nested inside of a rule are wrapped around the parent selector instead.
+  /// Ressource: <https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_nesting>
+  pub fn flatten_nesting(&mut self) {
+    self.rules = flatten_rules(mem::take(&mut self.rules));
+  }
+}
+
+fn flatten_rules(rules: Vec<Rule>) -> Vec<Rule> {
+  let mut flat = vec![];
+  for rule in rules {
+    match rule {
+      Rule::StyleRule(style_rule) => flatten_style_rule(style_rule, &mut flat),
+      Rule::AtRule(mut at_rule) => {
+        if let Some(AtRuleBlock::Rules(rules)) = at_rule.block {
+          at_rule.block = Some(AtRuleBlock::Rules(flatten_rules(rules)));
+        }
+        flat.push(Rule::AtRule(at_rule));
+      }
+    }
+  }
+  flat
+}
+
+// pushes the rule followed by its nested rules, whose selectors are resolved against the rule's selectors.
+fn flatten_style_rule(mut style_rule: StyleRule, flat: &mut Vec<Rule>) {
+  let nested_rules = mem::take(&mut style_rule.style.rules);
+  let selectors = style_rule.selectors.clone();
+  //a rule which only contains nested rules has nothing left to print.
+  if nested_rules.is_empty() || !style_rule.style.declarations.is_empty() {
+    flat.push(Rule::StyleRule(style_rule));
+  }
+
+  for nested_rule in nested_rules {
+    match nested_rule {
+      Rule::StyleRule(mut nested) => {
+        nested.selectors = resolve_selector_list(&nested.selectors, &selectors);
+        flatten_style_rule(nested, flat);
+      }
+      Rule::AtRule(AtRule {
+        name,
+        prelude,
+        block: Some(AtRuleBlock::Declarations(style)),
+      }) if !AtRule::has_declaration_block(&name) => {
+        // declarations directly inside of a nested @media apply to the parent selector.
+        let mut rules = vec![];
+        flatten_style_rule(
+          StyleRule {
+            selectors: selectors.clone(),
+            style,
+          },
+          &mut rules,
+        );
+        flat.push(Rule::AtRule(AtRule {
+          name,
+          prelude,
+          block: Some(AtRuleBlock::Rules(rules)),
+        }));
+      }
+      Rule::AtRule(at_rule) => flat.push(Rule::AtRule(at_rule)),
+    }
+  }
+}
+
+// every selector of the nested list combined with every selector of the parent list.
+// Each `&` stands for any of the parent selectors, so a selector with several of them is resolved for every combination,
+// e.g. `& + &` inside of `.a, .b` becomes `.a+.a,.a+.b,.b+.a,.b+.b`.
+fn resolve_selector_list(nested: &SelectorList, parent: &SelectorList) -> SelectorList {
+  let mut resolved = vec![];
+  for selector in nested.0.iter() {
+    let nestings = selector
+      .compounds
+      .iter()
+      .filter(|compound| compound.has_nesting())
+      .count();
+    for parents in combinations(&parent.0, nestings.max(1)) {
+      let selector = resolve_selector(selector, &parents);
+      if parents.iter().any(|parent| **parent == global_block()) {
+        resolved.push(into_global(selector));
+      } else {
+        resolved.push(selector);
+      }
+    }
+  }
+  SelectorList(resolved)
+}
+
+// every sequence of `len` selectors of the list.
+fn combinations(selectors: &[Selector], len: usize) -> Vec<Vec<&Selector>> {
+  (0..len).fold(vec![vec![]], |combinations, _| {
+    combinations
+      .into_iter()
+      .flat_map(|combination| {
+        selectors.iter().map(move |selector| {
+          let mut combination = combination.clone();
+          combination.push(selector);
+          combination
+        })
+      })
+      .collect()
+  })
+}
+
+// selector of a `:global { }` block, whose nested rules are left unscoped.
+fn global_block() -> Selector {
+  Selector {
+    compounds: vec![CompoundSelector {
+      combinator: None,
+      selectors: vec![global_marker()],
+    }],
+  }
+}
+
+fn global_marker() -> SimpleSelector {
+  SimpleSelector::PseudoClass {
+    name: "global".into(),
+    args: None,
+  }
+}
+
+// moves `:global` in front of the whole selector, e.g. `.dark :global` becomes `:global .dark`.
+fn into_global(mut selector: Selector) -> Selector {
+  for compound in selector.compounds.iter_mut() {
+    compound
+      .selectors
+      .retain(|simple_selector| *simple_selector != global_marker());
+  }
+  selector
+    .compounds
+    .retain(|compound| !compound.selectors.is_empty());
+  if let Some(first) = selector.compounds.first_mut() {
+    first.combinator = Some(Combinator::Descendant);
+  }
+  selector.compounds.splice(0..0, global_block().compounds);
+  selector
+}
+
+// replaces every `&` by its parent selector. Selectors without `&` are relative to the parent, e.g. `> p` is `& > p`.
+fn resolve_selector(nested: &Selector, parents: &[&Selector]) -> Selector {
+  let mut resolved = Selector::default();
+  if !nested
+    .compounds
+    .iter()
+    .any(|compound| compound.has_nesting())
+  {
+    resolved.compounds = parents[0].compounds.clone();
+    for (i, compound) in nested.compounds.iter().enumerate() {
+      let mut compound = compound.clone();
+      if i == 0 {
+        compound.combinator = Some(compound.combinator.unwrap_or(Combinator::Descendant));
+      }
+      resolved.compounds.push(compound);
+    }
+    return resolved;
+  }
+
+  let mut parents = parents.iter();
+  for (i, compound) in nested.compounds.iter().enumerate() {
+    if !compound.has_nesting() {
+      resolved.compounds.push(compound.clone());
+      continue;
+    }
+    let Some(parent) = parents.next() else {
+      continue;
+    };
+    // a parent made of several compounds can only be written out at the start of the selector,
+    // elsewhere its compounds would be related to the ones before them, e.g. `& + &` inside of `.a .b`
+    // is `.a .b+:is(.a .b)` rather than `.a .b+.a .b`.
+    let mut parent_compounds = if i != 0 && parent.compounds.len() > 1 {
+      vec![CompoundSelector {
+        combinator: None,
+        selectors: vec![SimpleSelector::PseudoClass {
+          name: "is".into(),
+          args: Some(parent.to_string()),
+        }],
+      }]
+    } else {
+      parent.compounds.clone()
+    };
+    if let Some(first) = parent_compounds.first_mut() {
+      first.combinator = compound.combinator;
+    }
+    if let Some(last) = parent_compounds.last_mut() {
+      merge_compound(last, compound);
+    }
+    resolved.compounds.extend(parent_compounds);
+  }
+  if let Some(first) = resolved.compounds.first_mut() {
+    first.combinator = None;
+  }
+  resolved
+}
+
+// adds the other simple selectors of a compound with `&` to the last compound of the parent, e.g. `&.one:hover`.
+// A type selector has to come first, e.g. `div&` inside of `.a` is `div.a`,
+// and the parent compound becomes an `:is()` if it has one of its own, e.g. `div&` inside of `p` is `div:is(p)`.
+fn merge_compound(last: &mut CompoundSelector, compound: &CompoundSelector) {
+  let (types, others): (Vec<_>, Vec<_>) = compound
+    .selectors
+    .iter()
+    .filter(|selector| **selector != SimpleSelector::Nesting)
+    .cloned()
+    .partition(is_type);
+  if !types.is_empty() && last.selectors.first().is_some_and(is_type) {
+    let parent = CompoundSelector {
+      combinator: None,
+      selectors: mem::take(&mut last.selectors),
+    };
+    last.selectors.push(SimpleSelector::PseudoClass {
+      name: "is".into(),
+      args: Some(parent.to_string()),
+    });
+  }
+  last.selectors.splice(0..0, types);
+  last.selectors.extend(others);
+}
+
+fn is_type(selector: &SimpleSelector) -> bool {
+  matches!(
+    selector,
+    SimpleSelector::Type(_) | SimpleSelector::Universal
+  )
+}
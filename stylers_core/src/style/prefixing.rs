@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+use std::mem;
+
+use crate::Targets;
+use crate::style::css_selector::{SelectorList, SimpleSelector};
+use crate::style::{
+  AtRule, AtRuleBlock, Declaration, Rule, StyleDeclaration, StyleRule, StyleSheet,
+};
+use crate::targets::{at_rule_variants, prefixed_selectors, property_variants, selector_variants};
+
+impl StyleSheet {
+  /// Adds the vendor prefixed variants the targeted browsers need, e.g. `-webkit-user-select: none;` before `user-select: none;`.
+  /// Prefixed pseudo-classes and pseudo-elements get a copy of their rule, e.g. `input::-moz-placeholder{...}` before `input::placeholder{...}`,
+  /// since browsers drop a whole rule with a selector they don't know. `@keyframes` get prefixed copies the same way.
+  /// Variants which are already written out aren't added again.
+  pub fn prefix(&mut self, targets: &Targets) {
+    if !targets.is_empty() {
+      prefix_rules(&mut self.rules, targets);
+    }
+  }
+}
+
+fn prefix_rules(rules: &mut Vec<Rule>, targets: &Targets) {
+  // at-rules already written out with a prefix, e.g. `@-webkit-keyframes spin`.
+  let written = rules
+    .iter()
+    .filter_map(|rule| match rule {
+      Rule::AtRule(at_rule) => Some((at_rule.name.clone(), at_rule.prelude.clone())),
+      Rule::StyleRule(_) => None,
+    })
+    .collect::<HashSet<_>>();
+  let mut prefixed = Vec::with_capacity(rules.len());
+  for mut rule in mem::take(rules) {
+    match &mut rule {
+      Rule::StyleRule(style_rule) => {
+        prefix_declarations(&mut style_rule.style, targets);
+        for selectors in selector_variants_of(&style_rule.selectors, targets) {
+          prefixed.push(Rule::StyleRule(StyleRule {
+            selectors,
+            style: style_rule.style.clone(),
+          }));
+        }
+      }
+      Rule::AtRule(at_rule) => {
+        match &mut at_rule.block {
+          Some(AtRuleBlock::Rules(rules)) => prefix_rules(rules, targets),
+          Some(AtRuleBlock::Declarations(style)) => prefix_declarations(style, targets),
+          None => {}
+        }
+        for variant in at_rule_variants(&at_rule.name) {
+          let name = format!("{}{}", variant.variant, at_rule.name);
+          if targets.needs(variant) && !written.contains(&(name.clone(), at_rule.prelude.clone())) {
+            prefixed.push(Rule::AtRule(AtRule {
+              name,
+              ..at_rule.clone()
+            }));
+          }
+        }
+      }
+    }
+    prefixed.push(rule);
+  }
+  *rules = prefixed;
+}
+
+fn prefix_declarations(style: &mut StyleDeclaration, targets: &Targets) {
+  let written = style
+    .declarations
+    .iter()
+    .map(|declaration| declaration.property.clone())
+    .collect::<HashSet<_>>();
+  let mut declarations = Vec::with_capacity(style.declarations.len());
+  for declaration in mem::take(&mut style.declarations) {
+    for variant in property_variants(&declaration.property) {
+      let property = format!("{}{}", variant.variant, declaration.property);
+      if targets.needs(variant) && !written.contains(&property) {
+        declarations.push(Declaration {
+          property,
+          ..declaration.clone()
+        });
+      }
+    }
+    declarations.push(declaration);
+  }
+  style.declarations = declarations;
+  prefix_rules(&mut style.rules, targets);
+}
+
+// selectors of the copies of a rule, one per needed variant of its pseudo-classes and pseudo-elements.
+fn selector_variants_of(selectors: &SelectorList, targets: &Targets) -> Vec<SelectorList> {
+  let mut lists = vec![];
+  for standard in prefixed_selectors() {
+    let standard_selector = pseudo_selector(standard);
+    if !contains(selectors, &standard_selector) {
+      continue;
+    }
+    for variant in selector_variants(standard) {
+      if !targets.needs(variant) {
+        continue;
+      }
+      let mut list = selectors.clone();
+      replace(
+        &mut list,
+        &standard_selector,
+        &pseudo_selector(variant.variant),
+      );
+      if !lists.contains(&list) {
+        lists.push(list);
+      }
+    }
+  }
+  lists
+}
+
+// `::name` or `:name` without arguments.
+fn pseudo_selector(selector: &str) -> SimpleSelector {
+  match selector.strip_prefix("::") {
+    Some(name) => SimpleSelector::PseudoElement {
+      name: name.to_string(),
+      args: None,
+    },
+    None => SimpleSelector::PseudoClass {
+      name: selector.trim_start_matches(':').to_string(),
+      args: None,
+    },
+  }
+}
+
+fn simple_selectors(list: &mut SelectorList) -> impl Iterator<Item = &mut SimpleSelector> {
+  list
+    .0
+    .iter_mut()
+    .flat_map(|selector| selector.compounds.iter_mut())
+    .flat_map(|compound| compound.selectors.iter_mut())
+}
+
+fn contains(list: &SelectorList, selector: &SimpleSelector) -> bool {
+  list
+    .0
+    .iter()
+    .flat_map(|selector| selector.compounds.iter())
+    .flat_map(|compound| compound.selectors.iter())
+    .any(|simple| simple == selector)
+}
+
+fn replace(list: &mut SelectorList, from: &SimpleSelector, to: &SimpleSelector) {
+  for simple in simple_selectors(list) {
+    if simple == from {
+      *simple = to.clone();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn prefixed(css: &str, targets: &str) -> String {
+    let mut style_sheet = css.parse::<StyleSheet>().unwrap();
+    style_sheet.prefix(&targets.parse().unwrap());
+    style_sheet.to_string()
+  }
+
+  #[test]
+  fn adds_variants_for_targets() {
+    let css = "p{user-select: none;color: red;}input::placeholder{color: gray;}";
+    assert_eq!(
+      prefixed(css, "firefox >= 50, safari >= 17"),
+      "p{-moz-user-select: none;-webkit-user-select: none;user-select: none;color: red;}\
+       input::-moz-placeholder{color: gray;}input::placeholder{color: gray;}"
+    );
+    assert_eq!(prefixed(css, "chrome >= 120"), css);
+    assert_eq!(prefixed(css, ""), css);
+    // already written variants aren't added again.
+    assert_eq!(
+      prefixed(
+        "p{-webkit-user-select: none;user-select: none;}",
+        "safari >= 17"
+      ),
+      "p{-webkit-user-select: none;user-select: none;}"
+    );
+    assert_eq!(
+      prefixed(
+        "@media print{@keyframes spin{to{transform: rotate(1turn);}}}",
+        "firefox 15"
+      ),
+      "@media print{@-moz-keyframes spin{to{-moz-transform: rotate(1turn);transform: rotate(1turn);}}\
+       @keyframes spin{to{-moz-transform: rotate(1turn);transform: rotate(1turn);}}}"
+    );
+  }
+}
@@ -0,0 +1,113 @@
+use crate::Indent;
+use crate::style::css_selector::{Combinator, SelectorList};
+use crate::style::{AtRuleBlock, Rule, StyleDeclaration, StyleSheet};
+
+impl StyleSheet {
+  /// Prints the css for reading it, with one selector and one declaration per line, blocks indented by `indent`
+  /// and a blank line between top level rules, e.g. `a {\n  color: red;\n}\n`.
+  /// The rules nested inside of a block follow each other without one.
+  pub fn to_pretty_string(&self, indent: Indent) -> String {
+    let mut css = String::new();
+    write_rules(&self.rules, &indent.to_string(), 0, &mut css);
+    css
+  }
+}
+
+fn write_rules(rules: &[Rule], indent: &str, depth: usize, css: &mut String) {
+  let prefix = indent.repeat(depth);
+  for (i, rule) in rules.iter().enumerate() {
+    if i != 0 && depth == 0 {
+      css.push('\n');
+    }
+    css.push_str(&prefix);
+    match rule {
+      Rule::StyleRule(style_rule) => {
+        write_selectors(&style_rule.selectors, &prefix, css);
+        css.push(' ');
+        write_declarations(&style_rule.style, indent, depth, css);
+      }
+      Rule::AtRule(at_rule) => {
+        css.push('@');
+        css.push_str(&at_rule.name);
+        if !at_rule.prelude.is_empty() {
+          css.push(' ');
+          css.push_str(&at_rule.prelude);
+        }
+        match &at_rule.block {
+          None => css.push_str(";\n"),
+          Some(AtRuleBlock::Declarations(style)) => {
+            css.push(' ');
+            write_declarations(style, indent, depth, css);
+          }
+          Some(AtRuleBlock::Rules(rules)) => {
+            css.push_str(" {\n");
+            write_rules(rules, indent, depth + 1, css);
+            css.push_str(&prefix);
+            css.push_str("}\n");
+          }
+        }
+      }
+    }
+  }
+}
+
+fn write_declarations(style: &StyleDeclaration, indent: &str, depth: usize, css: &mut String) {
+  let prefix = indent.repeat(depth + 1);
+  css.push_str("{\n");
+  for declaration in style.declarations.iter() {
+    css.push_str(&prefix);
+    css.push_str(&format!("{}\n", declaration));
+  }
+  write_rules(&style.rules, indent, depth + 1, css);
+  css.push_str(&indent.repeat(depth));
+  css.push_str("}\n");
+}
+
+// one selector per line, with spaces around combinators, e.g. `ul > li`.
+fn write_selectors(selectors: &SelectorList, prefix: &str, css: &mut String) {
+  for (i, selector) in selectors.0.iter().enumerate() {
+    if i != 0 {
+      css.push_str(",\n");
+      css.push_str(prefix);
+    }
+    for (j, compound) in selector.compounds.iter().enumerate() {
+      match compound.combinator {
+        Some(Combinator::Descendant) if j != 0 => css.push(' '),
+        Some(Combinator::Descendant) | None => {}
+        // relative selectors of nested rules start with their combinator, e.g. `> li`.
+        Some(combinator) if j == 0 => css.push_str(&format!("{} ", combinator)),
+        Some(combinator) => css.push_str(&format!(" {} ", combinator)),
+      }
+      for simple in compound.selectors.iter() {
+        css.push_str(&simple.to_string());
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prints_pretty_css() {
+    let style_sheet = "h1,ul>li{color: yellow;margin: 0 !important;&:hover{color: red;}}\
+                       @media screen and (max-width: 600px){p{color: blue;}a{color: red;}}@import url(\"a.css\");"
+      .parse::<StyleSheet>()
+      .unwrap();
+    assert_eq!(
+      style_sheet.to_pretty_string(Indent::default()),
+      "h1,\n\
+       ul > li {\n  color: yellow;\n  margin: 0 !important;\n  &:hover {\n    color: red;\n  }\n}\n\n\
+       @media screen and (max-width: 600px) {\n  p {\n    color: blue;\n  }\n  a {\n    color: red;\n  }\n}\n\n\
+       @import url(\"a.css\");\n"
+    );
+    assert_eq!(
+      "p{color: blue;}"
+        .parse::<StyleSheet>()
+        .unwrap()
+        .to_pretty_string(Indent::Tab),
+      "p {\n\tcolor: blue;\n}\n"
+    );
+  }
+}
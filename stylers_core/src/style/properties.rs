@@ -0,0 +1,94 @@
+//! Css properties known to stylers, generated from `data/properties.toml` by the build script.
+//! Regenerate the data with `node tools/css-data/generate.mjs`.
+
+/// Status of a property as documented by MDN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyStatus {
+  Standard,
+  /// Not supported by every browser yet, e.g. `anchor-scope`.
+  Experimental,
+  /// Browser specific, e.g. `-webkit-line-clamp`.
+  Nonstandard,
+  /// Deprecated or a legacy alias, e.g. `grid-gap`.
+  Obsolete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+  pub name: &'static str,
+  /// Inherited by default from the parent element, e.g. `color`.
+  pub inherited: bool,
+  /// Sets several longhand properties at once, e.g. `margin`.
+  pub shorthand: bool,
+  pub status: PropertyStatus,
+}
+
+include!(concat!(env!("OUT_DIR"), "/properties.rs"));
+
+/// Every known property, sorted by name.
+pub fn properties() -> &'static [Property] {
+  &PROPERTIES
+}
+
+/// Looks up a property by its exact name, e.g. `container-type`.
+pub fn property(name: &str) -> Option<&'static Property> {
+  PROPERTIES
+    .binary_search_by(|property| property.name.cmp(name))
+    .ok()
+    .map(|index| &PROPERTIES[index])
+}
+
+/// Vendor prefixes of css properties.
+pub const VENDOR_PREFIXES: [&str; 4] = ["-webkit-", "-moz-", "-ms-", "-o-"];
+
+/// Splits a vendor prefixed name, e.g. `-moz-appearance` into `-moz-` and `appearance`.
+pub fn split_vendor_prefix(name: &str) -> Option<(&'static str, &str)> {
+  VENDOR_PREFIXES
+    .iter()
+    .find_map(|prefix| Some((*prefix, name.strip_prefix(prefix)?)))
+}
+
+/// Vendor prefixes any browser supported the property with, e.g. `-moz-` and `-webkit-` for `appearance`.
+pub fn prefixes(name: &str) -> &'static [&'static str] {
+  PREFIXES
+    .binary_search_by(|(property, _)| (*property).cmp(name))
+    .map_or(&[], |index| PREFIXES[index].1)
+}
+
+/// Whether the name is a descriptor of an at-rule, e.g. `src` of `@font-face`.
+pub(crate) fn is_descriptor(name: &str) -> bool {
+  DESCRIPTORS.binary_search(&name).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn looks_up_properties() {
+    assert!(properties().is_sorted_by_key(|property| property.name));
+    let container_type = property("container-type").unwrap();
+    assert!(!container_type.inherited && !container_type.shorthand);
+    assert_eq!(container_type.status, PropertyStatus::Standard);
+    assert!(property("font").unwrap().shorthand);
+    assert!(property("color").unwrap().inherited);
+    assert_eq!(
+      property("grid-gap").unwrap().status,
+      PropertyStatus::Obsolete
+    );
+    assert!(property("zoomAndPan").is_none());
+    assert!(property("voice-family").unwrap().inherited);
+    assert!(property("pause").unwrap().shorthand);
+    // legacy aliases are prefixed variants, and the excluded properties are unknown.
+    assert!(property("-webkit-align-content").is_none());
+    assert!(prefixes("align-content").contains(&"-webkit-"));
+    assert!(property("box-reflect").is_none() && property("kerning").is_none());
+    assert!(is_descriptor("unicode-range"));
+    assert_eq!(prefixes("appearance"), ["-moz-", "-webkit-"]);
+    assert!(prefixes("color").is_empty());
+    assert_eq!(
+      split_vendor_prefix("-ms-overflow-style"),
+      Some(("-ms-", "overflow-style"))
+    );
+  }
+}
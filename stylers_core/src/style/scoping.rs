@@ -0,0 +1,489 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::Class;
+use crate::style::css_selector::{
+  Combinator, CompoundSelector, Selector, SelectorList, SimpleSelector,
+};
+use crate::style::{AtRuleBlock, Rule, StyleSheet};
+
+/// Which compound selectors of a selector get the class.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScopeStrategy {
+  /// Every compound, e.g. `div .one p` becomes `div.l-xxxxxx .one.l-xxxxxx p.l-xxxxxx`.
+  #[default]
+  Every,
+  /// Only the first compound, e.g. `div.l-xxxxxx .one p`,
+  /// so the descendants can be styled wherever they come from.
+  Root,
+  /// Only the last compound, e.g. `div .one p.l-xxxxxx`.
+  Last,
+}
+
+impl std::str::FromStr for ScopeStrategy {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "every" => Ok(ScopeStrategy::Every),
+      "root" => Ok(ScopeStrategy::Root),
+      "last" => Ok(ScopeStrategy::Last),
+      _ => Err(format!(
+        "Unknown scoping strategy {:?}, expected one of every, root or last",
+        s
+      )),
+    }
+  }
+}
+
+/// How the class is applied to a style sheet, see [`StyleSheet::scope`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScopeOptions {
+  /// Default strategy, a `@scoping` rule of the style sheet takes precedence.
+  pub strategy: ScopeStrategy,
+  /// Suffixes the custom properties declared in the style sheet with the class, e.g. `--gap` becomes `--gap-l-xxxxxx`.
+  pub scope_custom_properties: bool,
+  /// Custom properties starting with this prefix stay public even when they are scoped, e.g. `--theme-`.
+  pub public_custom_properties: Option<String>,
+}
+
+/// What the scoped selectors of a style sheet refer to, without the class added by scoping.
+/// The contents of `:deep()` and `:global()` and the document root aren't part of it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectorMap {
+  /// Unique compound selectors, e.g. `p.one:hover`.
+  pub compounds: BTreeSet<String>,
+  /// Type selectors, e.g. `p`.
+  pub tags: BTreeSet<String>,
+  /// Class names without the dot, e.g. `one`.
+  pub classes: BTreeSet<String>,
+  /// Ids without the hash, e.g. `two`.
+  pub ids: BTreeSet<String>,
+  /// Tags, classes and ids which are only referenced inside of `:not()`, written as selectors, e.g. `.b` of `.a:not(.b)`.
+  /// Such rules are meant for markup without them. They are part of the other fields as well.
+  pub negated: BTreeSet<String>,
+}
+
+impl SelectorMap {
+  fn insert(&mut self, compound: &CompoundSelector) {
+    self.compounds.insert(compound.to_string());
+    for selector in compound.selectors.iter() {
+      match selector {
+        SimpleSelector::Type(name) => self.tags.insert(name.clone()),
+        SimpleSelector::Class(name) => self.classes.insert(name.clone()),
+        SimpleSelector::Id(name) => self.ids.insert(name.clone()),
+        _ => false,
+      };
+    }
+  }
+
+  // what the arguments of a `:not()` refer to, collected apart until the whole style sheet is scoped.
+  fn insert_negated(&mut self, negated: SelectorMap) {
+    self.compounds.extend(negated.compounds);
+    self.negated.extend(negated.tags);
+    self
+      .negated
+      .extend(negated.classes.iter().map(|class| format!(".{}", class)));
+    self
+      .negated
+      .extend(negated.ids.iter().map(|id| format!("#{}", id)));
+    self.negated.extend(negated.negated);
+  }
+
+  // adds the negated tags, classes and ids to the others, only the ones referenced nowhere else stay negated.
+  fn merge_negated(&mut self) {
+    let negated = std::mem::take(&mut self.negated);
+    self.negated = negated
+      .into_iter()
+      .filter(|selector| match selector.as_bytes().first() {
+        Some(b'.') => self.classes.insert(selector[1..].to_string()),
+        Some(b'#') => self.ids.insert(selector[1..].to_string()),
+        _ => self.tags.insert(selector.clone()),
+      })
+      .collect();
+  }
+}
+
+/// What happens to the compound selectors picked by the strategy.
+pub(super) enum Scoper<'a> {
+  /// The class is added, see [`StyleSheet::scope`].
+  Class(&'a Class),
+  /// The class names are suffixed with the class, see [`StyleSheet::scope_module`].
+  /// Keeps track of the renamed class names.
+  Module(&'a Class, &'a mut BTreeMap<String, String>),
+}
+
+impl StyleSheet {
+  /// Adds the class to the selectors of the style sheet, including the ones nested inside at-rules.
+  /// Nested style rules are flattened first, see [`StyleSheet::flatten_nesting`].
+  /// The names of `@keyframes` are suffixed with the class, unless written as `:global(name)`.
+  /// Returns what the scoped selectors refer to.
+  pub fn scope(&mut self, class: &Class, options: &ScopeOptions) -> SelectorMap {
+    self.flatten_nesting();
+    let strategy = self.scoping.unwrap_or(options.strategy);
+    let mut sel_map = SelectorMap::default();
+    scope_rules(
+      &mut self.rules,
+      &mut Scoper::Class(class),
+      strategy,
+      &mut sel_map,
+    );
+    sel_map.merge_negated();
+    self.scope_keyframes(class);
+    if options.scope_custom_properties {
+      self.scope_custom_properties(class, options.public_custom_properties.as_deref());
+    }
+    sel_map
+  }
+}
+
+pub(super) fn scope_rules(
+  rules: &mut [Rule],
+  scoper: &mut Scoper,
+  strategy: ScopeStrategy,
+  sel_map: &mut SelectorMap,
+) {
+  for rule in rules.iter_mut() {
+    match rule {
+      Rule::StyleRule(style_rule) => {
+        style_rule.selectors =
+          scope_selector_list(&style_rule.selectors, scoper, strategy, sel_map);
+      }
+      Rule::AtRule(at_rule) => {
+        if let Some(AtRuleBlock::Rules(rules)) = &mut at_rule.block {
+          scope_rules(rules, scoper, strategy, sel_map);
+        }
+      }
+    }
+  }
+}
+
+fn scope_selector_list(
+  selectors: &SelectorList,
+  scoper: &mut Scoper,
+  strategy: ScopeStrategy,
+  sel_map: &mut SelectorMap,
+) -> SelectorList {
+  SelectorList(
+    selectors
+      .0
+      .iter()
+      .map(|selector| scope_selector(selector, scoper, strategy, sel_map))
+      .collect(),
+  )
+}
+
+fn scope_selector(
+  selector: &Selector,
+  scoper: &mut Scoper,
+  strategy: ScopeStrategy,
+  sel_map: &mut SelectorMap,
+) -> Selector {
+  let compounds = &selector.compounds;
+  // a bare `:global` leaves the rest of the selector unscoped, e.g. `:global .portal p`.
+  let global_from = compounds
+    .iter()
+    .position(|compound| compound.selectors.iter().any(is_global_marker))
+    .unwrap_or(compounds.len());
+  // the contents of :deep() and :global() and the document root never get the class.
+  let scopable: Vec<usize> = (0..global_from)
+    .filter(|&i| unscoped_selector(&compounds[i]).is_none() && !compounds[i].is_document_root())
+    .collect();
+  let scoped_indices = match strategy {
+    ScopeStrategy::Every => &scopable[..],
+    ScopeStrategy::Root => &scopable[..scopable.len().min(1)],
+    ScopeStrategy::Last => &scopable[scopable.len().saturating_sub(1)..],
+  };
+  let mut scoped = Selector::default();
+  // combinator in front of a bare `:global` which was left out.
+  let mut dropped_combinator = None;
+  for (i, compound) in compounds.iter().enumerate() {
+    let mut compound = compound.clone();
+    if let Some(combinator) = dropped_combinator.take()
+      && compound.combinator == Some(Combinator::Descendant)
+    {
+      compound.combinator = combinator;
+    }
+
+    // custom :deep() and :global() directives, their contents are used as they are.
+    if let Some(unscoped_selector) = unscoped_selector(&compound) {
+      for (j, unscoped_compound) in unscoped_selector.compounds.iter().enumerate() {
+        let mut unscoped_compound = unscoped_compound.clone();
+        if j == 0 {
+          unscoped_compound.combinator = compound.combinator;
+        }
+        scoped.compounds.push(unscoped_compound);
+      }
+      continue;
+    }
+
+    if i >= global_from {
+      compound
+        .selectors
+        .retain(|selector| !is_global_marker(selector));
+      if compound.selectors.is_empty() {
+        dropped_combinator = Some(compound.combinator);
+        continue;
+      }
+      scoped.compounds.push(inline_global(compound));
+      continue;
+    }
+
+    if !scoped_indices.contains(&i) {
+      // compounds left out by the strategy are still referenced by the style sheet, e.g. `.body` of `.card .body`.
+      if scopable.contains(&i) {
+        record_compound(&compound, sel_map);
+      }
+      scoped.compounds.push(inline_global(compound));
+      continue;
+    }
+
+    let mut unscoped = inline_global(compound.clone());
+    unscoped.combinator = None;
+    sel_map.insert(&unscoped);
+
+    scoped.compounds.push(inline_global(scope_compound(
+      &compound, scoper, strategy, sel_map,
+    )));
+  }
+  scoped
+}
+
+// records what a compound which doesn't get the class refers to, including the selector arguments of its pseudo classes.
+fn record_compound(compound: &CompoundSelector, sel_map: &mut SelectorMap) {
+  let mut unscoped = inline_global(compound.clone());
+  unscoped.combinator = None;
+  sel_map.insert(&unscoped);
+  for selector in unscoped.selectors.iter() {
+    if let SimpleSelector::PseudoClass {
+      name,
+      args: Some(args),
+    } = selector
+      && let Some((_, list)) = SimpleSelector::selector_args(name, args)
+    {
+      record_selector_args(name, &list, sel_map);
+    }
+  }
+}
+
+// records what the selector arguments of a pseudo class refer to, the ones of `:not()` are kept apart.
+fn record_selector_args(name: &str, list: &SelectorList, sel_map: &mut SelectorMap) {
+  let mut negated = SelectorMap::default();
+  let arg_map = if name == "not" {
+    &mut negated
+  } else {
+    &mut *sel_map
+  };
+  for compound in list.0.iter().flat_map(|selector| selector.compounds.iter()) {
+    if unscoped_selector(compound).is_none()
+      && !compound.is_document_root()
+      && !compound.selectors.iter().any(is_global_marker)
+    {
+      record_compound(compound, arg_map);
+    }
+  }
+  sel_map.insert_negated(negated);
+}
+
+// every class of a css module compound is suffixed with the class, e.g. `.btn` becomes `.btn-l-xxxxxx`.
+fn rename_classes(
+  compound: &mut CompoundSelector,
+  class: &Class,
+  names: &mut BTreeMap<String, String>,
+) {
+  for selector in compound.selectors.iter_mut() {
+    if let SimpleSelector::Class(name) = selector {
+      let renamed = names
+        .entry(name.clone())
+        .or_insert_with(|| format!("{}-{}", name, class.as_name()));
+      *name = renamed.clone();
+    }
+  }
+}
+
+fn is_global_marker(selector: &SimpleSelector) -> bool {
+  matches!(selector, SimpleSelector::PseudoClass { name, args: None } if name == "global")
+}
+
+// the selector of a compound consisting only of the custom `:deep()` or `:global()` pseudo class.
+fn unscoped_selector(compound: &CompoundSelector) -> Option<Selector> {
+  if let [
+    SimpleSelector::PseudoClass {
+      name,
+      args: Some(args),
+    },
+  ] = compound.selectors.as_slice()
+    && matches!(name.as_str(), "deep" | "global")
+    && let Ok(list) = SelectorList::parse(args)
+    && let [selector] = list.0.as_slice()
+  {
+    return Some(selector.clone());
+  }
+  None
+}
+
+// replaces `:global()` inside of a compound by its unscoped simple selectors, e.g. `.btn:global(.active)`.
+fn inline_global(mut compound: CompoundSelector) -> CompoundSelector {
+  compound.selectors = compound
+    .selectors
+    .into_iter()
+    .flat_map(|selector| {
+      if let SimpleSelector::PseudoClass {
+        name,
+        args: Some(args),
+      } = &selector
+        && name == "global"
+        && let Ok(list) = SelectorList::parse(args)
+        && let [global] = list.0.as_slice()
+        && let [global_compound] = global.compounds.as_slice()
+      {
+        return global_compound.selectors.clone();
+      }
+      vec![selector]
+    })
+    .collect();
+  compound
+}
+
+// The class is added after the type, id, class and attribute selectors but before any pseudo selector.
+// e.g. `p::before` becomes `p.l-xxxxxx::before`
+// Selector arguments of logical pseudo classes are scoped as well, e.g. `:not(.one)` becomes `:not(.one.l-xxxxxx)`,
+// and so is the `of` list of `:nth-child()`, e.g. `:nth-child(2n of .one.l-xxxxxx)`.
+// With the root and last strategies only the compound the strategy picked gets the class,
+// so only the last compound of the arguments, which is the same element, is scoped and `:has()` is left as it is.
+// A compound whose `:is()` or `:where()` arguments all got the class doesn't get it a second time, e.g. `:is(h1.l-xxxxxx,h2.l-xxxxxx)`.
+fn scope_compound(
+  compound: &CompoundSelector,
+  scoper: &mut Scoper,
+  strategy: ScopeStrategy,
+  sel_map: &mut SelectorMap,
+) -> CompoundSelector {
+  let mut compound = compound.clone();
+  let mut scoped_by_args = false;
+  for selector in compound.selectors.iter_mut() {
+    if let SimpleSelector::PseudoClass {
+      name,
+      args: Some(args),
+    } = selector
+      && let Some((before, list)) = SimpleSelector::selector_args(name, args)
+    {
+      let arg_strategy = match strategy {
+        ScopeStrategy::Every => ScopeStrategy::Every,
+        _ if name == "has" => {
+          record_selector_args(name, &list, sel_map);
+          continue;
+        }
+        _ => ScopeStrategy::Last,
+      };
+      let mut negated = SelectorMap::default();
+      let arg_map = if name == "not" {
+        &mut negated
+      } else {
+        &mut *sel_map
+      };
+      let list = SelectorList(
+        list
+          .0
+          .iter()
+          .map(|selector| scope_selector(selector, scoper, arg_strategy, arg_map))
+          .collect(),
+      );
+      sel_map.insert_negated(negated);
+      if let Scoper::Class(class) = scoper
+        && matches!(name.as_str(), "is" | "where" | "matches" | "any")
+      {
+        let class_selector = SimpleSelector::Class(class.as_name().to_string());
+        scoped_by_args |= list.0.iter().all(|selector| {
+          selector
+            .compounds
+            .last()
+            .is_some_and(|last| last.selectors.contains(&class_selector))
+        });
+      }
+      *args = format!("{}{}", before, list);
+    }
+  }
+
+  let class = match scoper {
+    Scoper::Class(class) => class,
+    Scoper::Module(class, names) => {
+      rename_classes(&mut compound, class, names);
+      return compound;
+    }
+  };
+  if scoped_by_args {
+    return compound;
+  }
+  let class_selector = SimpleSelector::Class(class.as_name().to_string());
+  //universal selector is replaced by the class.
+  if let Some(universal) = compound
+    .selectors
+    .iter()
+    .position(|selector| *selector == SimpleSelector::Universal)
+  {
+    compound.selectors[universal] = class_selector;
+    return compound;
+  }
+  let position = compound
+    .selectors
+    .iter()
+    .position(|selector| {
+      matches!(
+        selector,
+        SimpleSelector::PseudoClass { .. } | SimpleSelector::PseudoElement { .. }
+      )
+    })
+    .unwrap_or(compound.selectors.len());
+  compound.selectors.insert(position, class_selector);
+  compound
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sel_map(css: &str) -> SelectorMap {
+    let mut style_sheet = css.parse::<StyleSheet>().unwrap();
+    style_sheet.scope(&Class::new("test".into()), &ScopeOptions::default())
+  }
+
+  #[test]
+  fn keeps_negated_selectors_apart() {
+    let sel_map =
+      sel_map(".a:not(.b, #c), p:not(span) { color: red; } .c:not(.a) { color: blue; }");
+    assert_eq!(
+      sel_map.classes,
+      ["a", "b", "c"].map(String::from).into_iter().collect()
+    );
+    assert_eq!(sel_map.ids, ["c"].map(String::from).into_iter().collect());
+    assert_eq!(
+      sel_map.negated,
+      [".b", "#c", "span"].map(String::from).into_iter().collect()
+    );
+  }
+
+  #[test]
+  fn records_compounds_left_out_by_the_strategy() {
+    for scoping in ["root", "last"] {
+      let sel_map = sel_map(&format!(
+        "@scoping {};\n.card .body:not(.x) p, :global(.portal) #dialog {{ color: red; }}",
+        scoping
+      ));
+      assert_eq!(
+        sel_map.classes,
+        ["body", "card", "x"]
+          .map(String::from)
+          .into_iter()
+          .collect()
+      );
+      assert_eq!(
+        sel_map.ids,
+        ["dialog"].map(String::from).into_iter().collect()
+      );
+      assert_eq!(sel_map.tags, ["p"].map(String::from).into_iter().collect());
+      assert_eq!(
+        sel_map.negated,
+        [".x"].map(String::from).into_iter().collect()
+      );
+    }
+  }
+}
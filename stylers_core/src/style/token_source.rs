@@ -0,0 +1,221 @@
+use proc_macro2::{Delimiter, Group, Literal, Span, TokenTree};
+
+use crate::style::AtRule;
+
+/// Css text written back from the tokens of a `style!` macro, so that it can go through the same parser as css files.
+/// The span of every token is kept to point errors at the token they come from.
+pub(crate) struct TokenSource {
+  pub(crate) css: String,
+  // byte offset in `css` where each token starts, in increasing order.
+  spans: Vec<(usize, Span)>,
+  is_proc_macro: bool,
+  prev_end: Option<(usize, usize)>,
+}
+
+// how string literals are written in the css.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Context {
+  /// Style sheets and at-rules like `@media`: literals keep their quotes, e.g. `@charset "UTF-8";`.
+  Rules,
+  /// Blocks of style rules: quotes are removed unless the literal is wrapped with `raw_str()`.
+  Declarations,
+  /// Blocks of at-rules like `@font-face`: literals keep their quotes.
+  Verbatim,
+}
+
+impl TokenSource {
+  /// is_proc_macro: whether this is called from inside of a procedural macro,
+  /// which is needed to read the spans of the tokens.
+  pub(crate) fn new(
+    token_stream: impl IntoIterator<Item = TokenTree>,
+    is_proc_macro: bool,
+  ) -> Self {
+    let mut source = TokenSource {
+      css: String::new(),
+      spans: vec![],
+      is_proc_macro,
+      prev_end: None,
+    };
+    source.write_stream(token_stream.into_iter().collect(), Context::Rules);
+    source
+  }
+
+  /// Span of the token the css at `offset` comes from.
+  pub(crate) fn span_at(&self, offset: usize) -> Option<Span> {
+    let index = self.spans.partition_point(|(start, _)| *start <= offset);
+    let index = index.checked_sub(1)?;
+    Some(self.spans[index].1)
+  }
+
+  fn start(&self, span: Span) -> (usize, usize) {
+    if self.is_proc_macro {
+      let start = span.unwrap().start();
+      (start.line(), start.column())
+    } else {
+      let start = span.start();
+      (start.line, start.column)
+    }
+  }
+
+  fn end(&self, span: Span) -> (usize, usize) {
+    if self.is_proc_macro {
+      let end = span.unwrap().end();
+      (end.line(), end.column())
+    } else {
+      let end = span.end();
+      (end.line, end.column)
+    }
+  }
+
+  // whitespace between tokens is significant in css, e.g. `div .one` vs `div.one`.
+  // note: tokens created without a source location all share the same position, so no whitespace is written between them.
+  fn write(&mut self, text: &str, span: Span) {
+    let start = self.start(span);
+    if let Some(prev_end) = self.prev_end
+      && prev_end != start
+    {
+      self
+        .css
+        .push(if prev_end.0 == start.0 { ' ' } else { '\n' });
+    }
+    self.spans.push((self.css.len(), span));
+    self.css.push_str(text);
+    self.prev_end = Some(self.end(span));
+  }
+
+  fn write_stream(&mut self, tokens: Vec<TokenTree>, context: Context) {
+    // offset where the current rule starts, to find out which kind of block follows it.
+    let mut rule_start = self.css.len();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(tt) = tokens.next() {
+      match tt {
+        TokenTree::Group(group) => {
+          let group_context = match (context, group.delimiter()) {
+            (Context::Rules, Delimiter::Brace) => self.block_context(rule_start),
+            (context, _) => context,
+          };
+          self.write_group(&group, group_context, true);
+          if group.delimiter() == Delimiter::Brace {
+            rule_start = self.css.len();
+          }
+        }
+        TokenTree::Ident(ident) => {
+          let name = ident.to_string();
+          //raw_str() keeps the quotes of the literal inside of it.
+          if context == Context::Declarations
+            && name == "raw_str"
+            && let Some(TokenTree::Group(group)) = tokens.peek()
+            && group.delimiter() == Delimiter::Parenthesis
+          {
+            let group = group.clone();
+            tokens.next();
+            self.write("", ident.span());
+            self.prev_end = None;
+            self.write_group(&group, Context::Verbatim, false);
+            continue;
+          }
+          self.write(&name, ident.span());
+        }
+        TokenTree::Literal(literal) => {
+          let text = literal_text(&literal, context != Context::Declarations);
+          self.write(&text, literal.span());
+        }
+        TokenTree::Punct(punct) => {
+          self.write(&punct.as_char().to_string(), punct.span());
+          if punct.as_char() == ';' {
+            rule_start = self.css.len();
+          }
+        }
+      }
+    }
+  }
+
+  fn write_group(&mut self, group: &Group, context: Context, with_delimiters: bool) {
+    let (open, close) = match group.delimiter() {
+      Delimiter::Brace => ("{", "}"),
+      Delimiter::Parenthesis => ("(", ")"),
+      Delimiter::Bracket => ("[", "]"),
+      Delimiter::None => ("", ""),
+    };
+    if with_delimiters {
+      self.write(open, group.span_open());
+    }
+    self.write_stream(group.stream().into_iter().collect(), context);
+    if with_delimiters {
+      self.write(close, group.span_close());
+    } else {
+      self.prev_end = Some(self.end(group.span_close()));
+    }
+  }
+
+  // context of the block following the text written since `rule_start`.
+  fn block_context(&self, rule_start: usize) -> Context {
+    let Some(at_rule) = self.css[rule_start..].trim_start().strip_prefix('@') else {
+      return Context::Declarations;
+    };
+    let end = at_rule
+      .find(|c: char| c.is_whitespace() || c == '(' || c == '"' || c == '\'')
+      .unwrap_or(at_rule.len());
+    if AtRule::has_declaration_block(&at_rule[..end]) {
+      Context::Verbatim
+    } else {
+      Context::Rules
+    }
+  }
+}
+
+// we are trimming r and # because in some cases like "\1g34" is not valid rust syntax,
+// in those places user have to use r"\1g34" or r#"\1g"34"#.
+fn literal_text(literal: &Literal, keep_quotes: bool) -> String {
+  let text = literal.to_string();
+  let Some(content) = string_content(&text) else {
+    return text;
+  };
+  if !keep_quotes {
+    return content.to_string();
+  }
+  if content.contains('"') && !content.contains('\'') {
+    format!("'{}'", content)
+  } else {
+    format!("\"{}\"", content)
+  }
+}
+
+// content between the quotes of a string literal, e.g. `"a"`, `r"a"` or `r#"a"#`.
+fn string_content(literal: &str) -> Option<&str> {
+  let raw = literal.strip_prefix('r').unwrap_or(literal);
+  let hashes = raw.len() - raw.trim_start_matches('#').len();
+  let content = raw[hashes..].strip_prefix('"')?;
+  let content = content.get(..content.len().checked_sub(hashes)?)?;
+  content.strip_suffix('"')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use proc_macro2::TokenStream;
+
+  fn css(tokens: &str) -> String {
+    let token_stream: TokenStream = tokens.parse().expect("Expected valid tokens");
+    TokenSource::new(token_stream, false).css
+  }
+
+  #[test]
+  fn keeps_whitespace_between_tokens() {
+    assert_eq!(
+      css("div .one>p:hover{margin: -1px 10%;color:#1e90ff;}"),
+      "div .one>p:hover{margin: -1px 10%;color:#1e90ff;}"
+    );
+    assert_eq!(css("h2,\nh3 {}"), "h2,\nh3 {}");
+  }
+
+  #[test]
+  fn writes_literals_by_context() {
+    assert_eq!(
+      css(
+        r##"@charset "UTF-8"; a[title="x"] { font-family: "Arial"; content: raw_str(r#"a"b"#); } @font-face { font-family: "Arial"; }"##
+      ),
+      r#"@charset "UTF-8"; a[title="x"] { font-family: Arial; content: 'a"b'; } @font-face { font-family: "Arial"; }"#
+    );
+  }
+}
@@ -0,0 +1,1326 @@
+//! Validation of declaration values against the kinds of components each common property accepts,
+//! e.g. `color: redd` or `width: 10pxx`.
+//! Every top level component of the value has to be accepted by the property,
+//! the order and number of the components aren't checked.
+use std::ops::Range;
+
+use levenshtein::levenshtein;
+
+use crate::parser::tokenizer::{Token, TokenKind, tokenize};
+
+/// Kind of component accepted in the value of a property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Component {
+  /// Dimension with a length unit, or `0`.
+  Length,
+  Percentage,
+  Number,
+  Integer,
+  Angle,
+  Time,
+  Resolution,
+  /// `fr` of grid tracks.
+  Flex,
+  /// Named or system color, hex color or color function.
+  Color,
+  /// `url()` or an image function like `linear-gradient()`.
+  Image,
+  Quoted,
+  /// Any identifier, e.g. the name of an animation.
+  CustomIdent,
+  Keywords(&'static [&'static str]),
+}
+
+use Component::*;
+
+const CSS_WIDE_KEYWORDS: &[&str] = &["inherit", "initial", "unset", "revert", "revert-layer"];
+
+pub(crate) const LENGTH_UNITS: &[&str] = &[
+  "px", "em", "rem", "ex", "rex", "ch", "rch", "cap", "rcap", "ic", "ric", "lh", "rlh", "vw", "vh",
+  "vi", "vb", "vmin", "vmax", "svw", "svh", "svi", "svb", "svmin", "svmax", "lvw", "lvh", "lvi",
+  "lvb", "lvmin", "lvmax", "dvw", "dvh", "dvi", "dvb", "dvmin", "dvmax", "cqw", "cqh", "cqi",
+  "cqb", "cqmin", "cqmax", "cm", "mm", "q", "in", "pt", "pc",
+];
+const ANGLE_UNITS: &[&str] = &["deg", "grad", "rad", "turn"];
+const TIME_UNITS: &[&str] = &["s", "ms"];
+const RESOLUTION_UNITS: &[&str] = &["dpi", "dpcm", "dppx", "x"];
+const FLEX_UNITS: &[&str] = &["fr"];
+
+/// ref: <https://developer.mozilla.org/en-US/docs/Web/CSS/named-color>
+const NAMED_COLORS: &[&str] = &[
+  "aliceblue",
+  "antiquewhite",
+  "aqua",
+  "aquamarine",
+  "azure",
+  "beige",
+  "bisque",
+  "black",
+  "blanchedalmond",
+  "blue",
+  "blueviolet",
+  "brown",
+  "burlywood",
+  "cadetblue",
+  "chartreuse",
+  "chocolate",
+  "coral",
+  "cornflowerblue",
+  "cornsilk",
+  "crimson",
+  "cyan",
+  "darkblue",
+  "darkcyan",
+  "darkgoldenrod",
+  "darkgray",
+  "darkgreen",
+  "darkgrey",
+  "darkkhaki",
+  "darkmagenta",
+  "darkolivegreen",
+  "darkorange",
+  "darkorchid",
+  "darkred",
+  "darksalmon",
+  "darkseagreen",
+  "darkslateblue",
+  "darkslategray",
+  "darkslategrey",
+  "darkturquoise",
+  "darkviolet",
+  "deeppink",
+  "deepskyblue",
+  "dimgray",
+  "dimgrey",
+  "dodgerblue",
+  "firebrick",
+  "floralwhite",
+  "forestgreen",
+  "fuchsia",
+  "gainsboro",
+  "ghostwhite",
+  "gold",
+  "goldenrod",
+  "gray",
+  "green",
+  "greenyellow",
+  "grey",
+  "honeydew",
+  "hotpink",
+  "indianred",
+  "indigo",
+  "ivory",
+  "khaki",
+  "lavender",
+  "lavenderblush",
+  "lawngreen",
+  "lemonchiffon",
+  "lightblue",
+  "lightcoral",
+  "lightcyan",
+  "lightgoldenrodyellow",
+  "lightgray",
+  "lightgreen",
+  "lightgrey",
+  "lightpink",
+  "lightsalmon",
+  "lightseagreen",
+  "lightskyblue",
+  "lightslategray",
+  "lightslategrey",
+  "lightsteelblue",
+  "lightyellow",
+  "lime",
+  "limegreen",
+  "linen",
+  "magenta",
+  "maroon",
+  "mediumaquamarine",
+  "mediumblue",
+  "mediumorchid",
+  "mediumpurple",
+  "mediumseagreen",
+  "mediumslateblue",
+  "mediumspringgreen",
+  "mediumturquoise",
+  "mediumvioletred",
+  "midnightblue",
+  "mintcream",
+  "mistyrose",
+  "moccasin",
+  "navajowhite",
+  "navy",
+  "oldlace",
+  "olive",
+  "olivedrab",
+  "orange",
+  "orangered",
+  "orchid",
+  "palegoldenrod",
+  "palegreen",
+  "paleturquoise",
+  "palevioletred",
+  "papayawhip",
+  "peachpuff",
+  "peru",
+  "pink",
+  "plum",
+  "powderblue",
+  "purple",
+  "rebeccapurple",
+  "red",
+  "rosybrown",
+  "royalblue",
+  "saddlebrown",
+  "salmon",
+  "sandybrown",
+  "seagreen",
+  "seashell",
+  "sienna",
+  "silver",
+  "skyblue",
+  "slateblue",
+  "slategray",
+  "slategrey",
+  "snow",
+  "springgreen",
+  "steelblue",
+  "tan",
+  "teal",
+  "thistle",
+  "tomato",
+  "turquoise",
+  "violet",
+  "wheat",
+  "white",
+  "whitesmoke",
+  "yellow",
+  "yellowgreen",
+  "transparent",
+  "currentcolor",
+  "accentcolor",
+  "accentcolortext",
+  "activetext",
+  "buttonborder",
+  "buttonface",
+  "buttontext",
+  "canvas",
+  "canvastext",
+  "field",
+  "fieldtext",
+  "graytext",
+  "highlight",
+  "highlighttext",
+  "linktext",
+  "mark",
+  "marktext",
+  "selecteditem",
+  "selecteditemtext",
+  "visitedtext",
+];
+
+const COLOR_FUNCTIONS: &[&str] = &[
+  "rgb",
+  "rgba",
+  "hsl",
+  "hsla",
+  "hwb",
+  "lab",
+  "lch",
+  "oklab",
+  "oklch",
+  "color",
+  "color-mix",
+  "light-dark",
+  "contrast-color",
+];
+const IMAGE_FUNCTIONS: &[&str] = &[
+  "linear-gradient",
+  "radial-gradient",
+  "conic-gradient",
+  "repeating-linear-gradient",
+  "repeating-radial-gradient",
+  "repeating-conic-gradient",
+  "image-set",
+  "-webkit-image-set",
+  "cross-fade",
+  "image",
+  "element",
+  "paint",
+  "-webkit-gradient",
+  "-webkit-linear-gradient",
+  "-webkit-radial-gradient",
+  "-moz-linear-gradient",
+  "-moz-radial-gradient",
+];
+const MATH_FUNCTIONS: &[&str] = &[
+  "calc",
+  "min",
+  "max",
+  "clamp",
+  "round",
+  "mod",
+  "rem",
+  "abs",
+  "sign",
+  "sin",
+  "cos",
+  "tan",
+  "asin",
+  "acos",
+  "atan",
+  "atan2",
+  "pow",
+  "sqrt",
+  "hypot",
+  "log",
+  "exp",
+  "-webkit-calc",
+  "-moz-calc",
+];
+/// Functions which can stand for anything, their values are never checked.
+const SUBSTITUTION_FUNCTIONS: &[&str] = &["var", "env", "attr", "if", "inherit"];
+
+const AUTO: Component = Keywords(&["auto"]);
+const NONE: Component = Keywords(&["none"]);
+const NORMAL: Component = Keywords(&["normal"]);
+const SIZING: Component = Keywords(&[
+  "auto",
+  "min-content",
+  "max-content",
+  "fit-content",
+  "stretch",
+  "contain",
+]);
+const MAX_SIZING: Component = Keywords(&[
+  "none",
+  "min-content",
+  "max-content",
+  "fit-content",
+  "stretch",
+  "contain",
+]);
+const LINE_WIDTH: Component = Keywords(&["thin", "medium", "thick"]);
+const LINE_STYLE: Component = Keywords(&[
+  "none", "hidden", "dotted", "dashed", "solid", "double", "groove", "ridge", "inset", "outset",
+]);
+const BOX: Component = Keywords(&[
+  "border-box",
+  "padding-box",
+  "content-box",
+  "text",
+  "border-area",
+]);
+const POSITION: Component = Keywords(&["left", "center", "right", "top", "bottom"]);
+const REPEAT: Component = Keywords(&[
+  "repeat-x",
+  "repeat-y",
+  "repeat",
+  "space",
+  "round",
+  "no-repeat",
+]);
+const ATTACHMENT: Component = Keywords(&["scroll", "fixed", "local"]);
+const BLEND_MODE: Component = Keywords(&[
+  "normal",
+  "multiply",
+  "screen",
+  "overlay",
+  "darken",
+  "lighten",
+  "color-dodge",
+  "color-burn",
+  "hard-light",
+  "soft-light",
+  "difference",
+  "exclusion",
+  "hue",
+  "saturation",
+  "color",
+  "luminosity",
+  "plus-darker",
+  "plus-lighter",
+]);
+const OVERFLOW: Component = Keywords(&["visible", "hidden", "clip", "scroll", "auto", "overlay"]);
+const CONTENT_ALIGN: Component = Keywords(&[
+  "normal",
+  "start",
+  "end",
+  "center",
+  "flex-start",
+  "flex-end",
+  "left",
+  "right",
+  "baseline",
+  "first",
+  "last",
+  "space-between",
+  "space-around",
+  "space-evenly",
+  "stretch",
+  "safe",
+  "unsafe",
+]);
+const SELF_ALIGN: Component = Keywords(&[
+  "auto",
+  "normal",
+  "stretch",
+  "baseline",
+  "first",
+  "last",
+  "center",
+  "start",
+  "end",
+  "self-start",
+  "self-end",
+  "flex-start",
+  "flex-end",
+  "left",
+  "right",
+  "safe",
+  "unsafe",
+  "anchor-center",
+  "legacy",
+]);
+const EASING: Component = Keywords(&[
+  "linear",
+  "ease",
+  "ease-in",
+  "ease-out",
+  "ease-in-out",
+  "step-start",
+  "step-end",
+]);
+const FONT_SIZE: Component = Keywords(&[
+  "xx-small",
+  "x-small",
+  "small",
+  "medium",
+  "large",
+  "x-large",
+  "xx-large",
+  "xxx-large",
+  "larger",
+  "smaller",
+  "math",
+]);
+const FONT_WEIGHT: Component = Keywords(&["normal", "bold", "bolder", "lighter"]);
+const FONT_STYLE: Component = Keywords(&["normal", "italic", "oblique", "left", "right"]);
+const GENERIC_FAMILY: Component = Keywords(&[
+  "serif",
+  "sans-serif",
+  "monospace",
+  "cursive",
+  "fantasy",
+  "system-ui",
+  "ui-serif",
+  "ui-sans-serif",
+  "ui-monospace",
+  "ui-rounded",
+  "math",
+  "emoji",
+  "fangsong",
+]);
+const CURSOR: Component = Keywords(&[
+  "auto",
+  "default",
+  "none",
+  "context-menu",
+  "help",
+  "pointer",
+  "progress",
+  "wait",
+  "cell",
+  "crosshair",
+  "text",
+  "vertical-text",
+  "alias",
+  "copy",
+  "move",
+  "no-drop",
+  "not-allowed",
+  "grab",
+  "grabbing",
+  "all-scroll",
+  "col-resize",
+  "row-resize",
+  "n-resize",
+  "e-resize",
+  "s-resize",
+  "w-resize",
+  "ne-resize",
+  "nw-resize",
+  "se-resize",
+  "sw-resize",
+  "ew-resize",
+  "ns-resize",
+  "nesw-resize",
+  "nwse-resize",
+  "zoom-in",
+  "zoom-out",
+]);
+const DISPLAY: Component = Keywords(&[
+  "block",
+  "inline",
+  "run-in",
+  "flow",
+  "flow-root",
+  "table",
+  "flex",
+  "grid",
+  "ruby",
+  "list-item",
+  "table-row-group",
+  "table-header-group",
+  "table-footer-group",
+  "table-row",
+  "table-cell",
+  "table-column-group",
+  "table-column",
+  "table-caption",
+  "ruby-base",
+  "ruby-text",
+  "ruby-base-container",
+  "ruby-text-container",
+  "contents",
+  "none",
+  "inline-block",
+  "inline-table",
+  "inline-flex",
+  "inline-grid",
+  "math",
+]);
+const SHADOW: Component = Keywords(&["none", "inset"]);
+const PAINT: Component = Keywords(&["none", "context-fill", "context-stroke"]);
+const TEXT_DECORATION_LINE: Component = Keywords(&[
+  "none",
+  "underline",
+  "overline",
+  "line-through",
+  "blink",
+  "spelling-error",
+  "grammar-error",
+]);
+const TEXT_DECORATION_STYLE: Component = Keywords(&["solid", "double", "dotted", "dashed", "wavy"]);
+const BREAK: Component = Keywords(&[
+  "auto",
+  "avoid",
+  "always",
+  "all",
+  "avoid-page",
+  "page",
+  "left",
+  "right",
+  "recto",
+  "verso",
+  "avoid-column",
+  "column",
+  "avoid-region",
+  "region",
+]);
+const WHITE_SPACE: Component = Keywords(&[
+  "normal",
+  "pre",
+  "nowrap",
+  "pre-wrap",
+  "pre-line",
+  "break-spaces",
+  "collapse",
+  "preserve",
+  "preserve-breaks",
+  "preserve-spaces",
+  "wrap",
+  "balance",
+  "stable",
+  "pretty",
+]);
+const VERTICAL_ALIGN: Component = Keywords(&[
+  "baseline",
+  "sub",
+  "super",
+  "text-top",
+  "text-bottom",
+  "middle",
+  "top",
+  "bottom",
+  "center",
+  "first",
+  "last",
+  "alphabetic",
+  "ideographic",
+  "central",
+]);
+
+/// Kinds of components accepted by the common properties, properties not listed here aren't checked.
+/// Vendor prefixes are ignored, e.g. `-webkit-box-shadow` is checked as `box-shadow`.
+fn components(property: &str) -> Option<&'static [Component]> {
+  let components: &'static [Component] = match property {
+    "color"
+    | "background-color"
+    | "border-top-color"
+    | "border-right-color"
+    | "border-bottom-color"
+    | "border-left-color"
+    | "border-block-color"
+    | "border-block-start-color"
+    | "border-block-end-color"
+    | "border-inline-color"
+    | "border-inline-start-color"
+    | "border-inline-end-color"
+    | "border-color"
+    | "column-rule-color"
+    | "text-decoration-color"
+    | "text-emphasis-color"
+    | "stop-color"
+    | "flood-color"
+    | "lighting-color"
+    | "text-fill-color"
+    | "text-stroke-color"
+    | "tap-highlight-color" => &[Color],
+    "outline-color" => &[Color, Keywords(&["auto", "invert"])],
+    "caret-color" | "accent-color" | "scrollbar-color" => &[Color, AUTO],
+    "fill" | "stroke" => &[Color, Image, PAINT],
+    "box-shadow" => &[Color, Length, SHADOW],
+    "text-shadow" => &[Color, Length, NONE],
+    "width" | "height" | "min-width" | "min-height" | "inline-size" | "block-size"
+    | "min-inline-size" | "min-block-size" => &[Length, Percentage, SIZING],
+    "max-width" | "max-height" | "max-inline-size" | "max-block-size" => {
+      &[Length, Percentage, MAX_SIZING]
+    }
+    "top"
+    | "right"
+    | "bottom"
+    | "left"
+    | "inset"
+    | "inset-block"
+    | "inset-inline"
+    | "inset-block-start"
+    | "inset-block-end"
+    | "inset-inline-start"
+    | "inset-inline-end"
+    | "margin"
+    | "margin-top"
+    | "margin-right"
+    | "margin-bottom"
+    | "margin-left"
+    | "margin-block"
+    | "margin-inline"
+    | "margin-block-start"
+    | "margin-block-end"
+    | "margin-inline-start"
+    | "margin-inline-end"
+    | "scroll-padding"
+    | "scroll-padding-top"
+    | "scroll-padding-right"
+    | "scroll-padding-bottom"
+    | "scroll-padding-left"
+    | "scroll-padding-block"
+    | "scroll-padding-inline"
+    | "scroll-padding-block-start"
+    | "scroll-padding-block-end"
+    | "scroll-padding-inline-start"
+    | "scroll-padding-inline-end" => &[Length, Percentage, AUTO],
+    "padding"
+    | "padding-top"
+    | "padding-right"
+    | "padding-bottom"
+    | "padding-left"
+    | "padding-block"
+    | "padding-inline"
+    | "padding-block-start"
+    | "padding-block-end"
+    | "padding-inline-start"
+    | "padding-inline-end"
+    | "border-radius"
+    | "border-top-left-radius"
+    | "border-top-right-radius"
+    | "border-bottom-right-radius"
+    | "border-bottom-left-radius"
+    | "border-start-start-radius"
+    | "border-start-end-radius"
+    | "border-end-start-radius"
+    | "border-end-end-radius"
+    | "shape-margin"
+    | "cx"
+    | "cy"
+    | "r"
+    | "x"
+    | "y" => &[Length, Percentage],
+    "rx" | "ry" => &[Length, Percentage, AUTO],
+    "scroll-margin"
+    | "scroll-margin-top"
+    | "scroll-margin-right"
+    | "scroll-margin-bottom"
+    | "scroll-margin-left"
+    | "scroll-margin-block"
+    | "scroll-margin-inline"
+    | "scroll-margin-block-start"
+    | "scroll-margin-block-end"
+    | "scroll-margin-inline-start"
+    | "scroll-margin-inline-end" => &[Length],
+    "gap" | "row-gap" | "column-gap" | "grid-gap" | "grid-row-gap" | "grid-column-gap" => {
+      &[Length, Percentage, NORMAL]
+    }
+    "border-width"
+    | "border-top-width"
+    | "border-right-width"
+    | "border-bottom-width"
+    | "border-left-width"
+    | "border-block-width"
+    | "border-inline-width"
+    | "border-block-start-width"
+    | "border-block-end-width"
+    | "border-inline-start-width"
+    | "border-inline-end-width"
+    | "outline-width"
+    | "column-rule-width"
+    | "text-stroke-width" => &[Length, LINE_WIDTH],
+    "border-style"
+    | "border-top-style"
+    | "border-right-style"
+    | "border-bottom-style"
+    | "border-left-style"
+    | "border-block-style"
+    | "border-inline-style"
+    | "border-block-start-style"
+    | "border-block-end-style"
+    | "border-inline-start-style"
+    | "border-inline-end-style"
+    | "column-rule-style" => &[LINE_STYLE],
+    "outline-style" => &[LINE_STYLE, AUTO],
+    "border"
+    | "border-top"
+    | "border-right"
+    | "border-bottom"
+    | "border-left"
+    | "border-block"
+    | "border-inline"
+    | "border-block-start"
+    | "border-block-end"
+    | "border-inline-start"
+    | "border-inline-end"
+    | "column-rule" => &[Length, LINE_WIDTH, LINE_STYLE, Color],
+    "outline" => &[
+      Length,
+      LINE_WIDTH,
+      LINE_STYLE,
+      Color,
+      Keywords(&["auto", "invert"]),
+    ],
+    "outline-offset" | "perspective-origin" | "transform-origin" => &[Length, Percentage, POSITION],
+    "font-size" => &[Length, Percentage, FONT_SIZE],
+    "line-height" => &[Length, Percentage, Number, NORMAL],
+    "letter-spacing" | "word-spacing" => &[Length, Percentage, NORMAL],
+    "text-indent" => &[Length, Percentage, Keywords(&["hanging", "each-line"])],
+    "text-underline-offset" => &[Length, Percentage, AUTO],
+    "text-decoration-thickness" => &[Length, Percentage, Keywords(&["auto", "from-font"])],
+    "tab-size" => &[Length, Number],
+    "column-width" => &[Length, AUTO],
+    "column-count" => &[Integer, AUTO],
+    "columns" => &[Length, Integer, AUTO],
+    "perspective" => &[Length, NONE],
+    "stroke-width" | "stroke-dashoffset" => &[Length, Percentage, Number],
+    "stroke-dasharray" => &[Length, Percentage, Number, NONE],
+    "opacity"
+    | "fill-opacity"
+    | "stroke-opacity"
+    | "stop-opacity"
+    | "flood-opacity"
+    | "shape-image-threshold" => &[Number, Percentage],
+    "stroke-miterlimit" | "flex-grow" | "flex-shrink" => &[Number],
+    "z-index" => &[Integer, AUTO],
+    "order" | "orphans" | "widows" => &[Integer],
+    "line-clamp" => &[Integer, NONE],
+    "font-weight" => &[Number, FONT_WEIGHT],
+    "font-style" => &[Angle, FONT_STYLE],
+    "font-stretch" => &[
+      Percentage,
+      Keywords(&[
+        "normal",
+        "ultra-condensed",
+        "extra-condensed",
+        "condensed",
+        "semi-condensed",
+        "semi-expanded",
+        "expanded",
+        "extra-expanded",
+        "ultra-expanded",
+      ]),
+    ],
+    "font-family" => &[Quoted, CustomIdent, GENERIC_FAMILY],
+    "aspect-ratio" => &[Number, AUTO],
+    "scale" => &[Number, Percentage, NONE],
+    "rotate" => &[Angle, Number, Keywords(&["none", "x", "y", "z"])],
+    "translate" => &[Length, Percentage, NONE],
+    "zoom" => &[Number, Percentage, NORMAL],
+    "flex" => &[
+      Number,
+      Length,
+      Percentage,
+      Keywords(&[
+        "none",
+        "auto",
+        "content",
+        "min-content",
+        "max-content",
+        "fit-content",
+      ]),
+    ],
+    "flex-basis" => &[Length, Percentage, SIZING, Keywords(&["content"])],
+    "flex-direction" => &[Keywords(&[
+      "row",
+      "row-reverse",
+      "column",
+      "column-reverse",
+    ])],
+    "flex-wrap" => &[Keywords(&["nowrap", "wrap", "wrap-reverse"])],
+    "flex-flow" => &[Keywords(&[
+      "row",
+      "row-reverse",
+      "column",
+      "column-reverse",
+      "nowrap",
+      "wrap",
+      "wrap-reverse",
+    ])],
+    "justify-content" | "align-content" | "place-content" => &[CONTENT_ALIGN],
+    "align-items" | "align-self" | "justify-items" | "justify-self" | "place-items"
+    | "place-self" => &[SELF_ALIGN],
+    "grid-auto-flow" => &[Keywords(&["row", "column", "dense"])],
+    "grid-auto-rows" | "grid-auto-columns" => &[
+      Length,
+      Percentage,
+      Flex,
+      Keywords(&["auto", "min-content", "max-content"]),
+    ],
+    "display" => &[DISPLAY],
+    "position" => &[Keywords(&[
+      "static", "relative", "absolute", "fixed", "sticky",
+    ])],
+    "float" => &[Keywords(&[
+      "left",
+      "right",
+      "none",
+      "inline-start",
+      "inline-end",
+    ])],
+    "clear" => &[Keywords(&[
+      "left",
+      "right",
+      "both",
+      "none",
+      "inline-start",
+      "inline-end",
+    ])],
+    "visibility" => &[Keywords(&["visible", "hidden", "collapse"])],
+    "overflow" | "overflow-x" | "overflow-y" | "overflow-block" | "overflow-inline" => &[OVERFLOW],
+    "box-sizing" => &[Keywords(&["content-box", "border-box"])],
+    "text-align" | "text-align-last" => &[Keywords(&[
+      "start",
+      "end",
+      "left",
+      "right",
+      "center",
+      "justify",
+      "match-parent",
+      "justify-all",
+      "auto",
+    ])],
+    "text-transform" => &[Keywords(&[
+      "none",
+      "capitalize",
+      "uppercase",
+      "lowercase",
+      "full-width",
+      "full-size-kana",
+      "math-auto",
+    ])],
+    "white-space" | "white-space-collapse" | "text-wrap" | "text-wrap-mode" | "text-wrap-style" => {
+      &[WHITE_SPACE, AUTO]
+    }
+    "word-break" => &[Keywords(&[
+      "normal",
+      "break-all",
+      "keep-all",
+      "break-word",
+      "auto-phrase",
+    ])],
+    "overflow-wrap" | "word-wrap" => &[Keywords(&["normal", "break-word", "anywhere"])],
+    "text-overflow" => &[Quoted, Keywords(&["clip", "ellipsis", "fade"])],
+    "hyphens" => &[Keywords(&["none", "manual", "auto"])],
+    "vertical-align" => &[Length, Percentage, VERTICAL_ALIGN],
+    "text-decoration-line" => &[TEXT_DECORATION_LINE],
+    "text-decoration-style" => &[TEXT_DECORATION_STYLE],
+    "text-decoration" => &[
+      Length,
+      Percentage,
+      Color,
+      TEXT_DECORATION_LINE,
+      TEXT_DECORATION_STYLE,
+      Keywords(&["auto", "from-font"]),
+    ],
+    "cursor" => &[Image, Number, CURSOR],
+    "pointer-events" => &[Keywords(&[
+      "auto",
+      "none",
+      "visiblepainted",
+      "visiblefill",
+      "visiblestroke",
+      "visible",
+      "painted",
+      "fill",
+      "stroke",
+      "bounding-box",
+      "all",
+    ])],
+    "user-select" => &[Keywords(&["auto", "text", "none", "contain", "all"])],
+    "resize" => &[Keywords(&[
+      "none",
+      "both",
+      "horizontal",
+      "vertical",
+      "block",
+      "inline",
+    ])],
+    "object-fit" => &[Keywords(&[
+      "fill",
+      "contain",
+      "cover",
+      "none",
+      "scale-down",
+    ])],
+    "object-position"
+    | "background-position"
+    | "background-position-x"
+    | "background-position-y"
+    | "mask-position" => &[Length, Percentage, POSITION],
+    "background-image" | "mask-image" | "list-style-image" | "border-image-source" => {
+      &[Image, NONE]
+    }
+    "background-repeat" | "mask-repeat" => &[REPEAT],
+    "background-attachment" => &[ATTACHMENT],
+    "background-clip" | "background-origin" | "mask-clip" | "mask-origin" => &[BOX],
+    "background-size" | "mask-size" => {
+      &[Length, Percentage, Keywords(&["auto", "cover", "contain"])]
+    }
+    "background" => &[
+      Color,
+      Image,
+      Length,
+      Percentage,
+      NONE,
+      POSITION,
+      REPEAT,
+      ATTACHMENT,
+      BOX,
+      Keywords(&["auto", "cover", "contain"]),
+    ],
+    "background-blend-mode" | "mix-blend-mode" => &[BLEND_MODE],
+    "isolation" => &[Keywords(&["auto", "isolate"])],
+    "backface-visibility" => &[Keywords(&["visible", "hidden"])],
+    "transform-style" => &[Keywords(&["flat", "preserve-3d"])],
+    "border-collapse" => &[Keywords(&["collapse", "separate"])],
+    "border-spacing" => &[Length],
+    "table-layout" => &[Keywords(&["auto", "fixed"])],
+    "caption-side" => &[Keywords(&["top", "bottom"])],
+    "empty-cells" => &[Keywords(&["show", "hide"])],
+    "direction" => &[Keywords(&["ltr", "rtl"])],
+    "writing-mode" => &[Keywords(&[
+      "horizontal-tb",
+      "vertical-rl",
+      "vertical-lr",
+      "sideways-rl",
+      "sideways-lr",
+    ])],
+    "list-style-position" => &[Keywords(&["inside", "outside"])],
+    "list-style-type" => &[Quoted, CustomIdent],
+    "scroll-behavior" => &[Keywords(&["auto", "smooth"])],
+    "overscroll-behavior"
+    | "overscroll-behavior-x"
+    | "overscroll-behavior-y"
+    | "overscroll-behavior-block"
+    | "overscroll-behavior-inline" => &[Keywords(&["auto", "contain", "none"])],
+    "scroll-snap-align" => &[Keywords(&["none", "start", "end", "center"])],
+    "scroll-snap-stop" => &[Keywords(&["normal", "always"])],
+    "scroll-snap-type" => &[Keywords(&[
+      "none",
+      "x",
+      "y",
+      "block",
+      "inline",
+      "both",
+      "mandatory",
+      "proximity",
+    ])],
+    "content-visibility" => &[Keywords(&["visible", "auto", "hidden"])],
+    "container-type" => &[Keywords(&["normal", "size", "inline-size", "scroll-state"])],
+    "container-name" | "anchor-name" | "view-transition-name" | "view-transition-class" => {
+      &[CustomIdent, NONE]
+    }
+    "animation-name" | "transition-property" | "will-change" => &[CustomIdent],
+    "transition-duration" | "transition-delay" | "animation-delay" => &[Time],
+    "animation-duration" => &[Time, AUTO],
+    "transition-timing-function" | "animation-timing-function" => &[EASING],
+    "animation-iteration-count" => &[Number, Keywords(&["infinite"])],
+    "animation-direction" => &[Keywords(&[
+      "normal",
+      "reverse",
+      "alternate",
+      "alternate-reverse",
+    ])],
+    "animation-fill-mode" => &[Keywords(&["none", "forwards", "backwards", "both"])],
+    "animation-play-state" => &[Keywords(&["running", "paused"])],
+    "transition" => &[Time, CustomIdent],
+    "animation" => &[Time, Number, CustomIdent],
+    "break-before" | "break-after" | "break-inside" | "page-break-before" | "page-break-after"
+    | "page-break-inside" => &[BREAK],
+    "column-span" => &[Integer, Keywords(&["none", "all"])],
+    "column-fill" => &[Keywords(&["auto", "balance", "balance-all"])],
+    "image-rendering" => &[Keywords(&[
+      "auto",
+      "smooth",
+      "high-quality",
+      "pixelated",
+      "crisp-edges",
+      "optimizespeed",
+      "optimizequality",
+    ])],
+    "image-resolution" => &[Resolution, Keywords(&["from-image", "snap"])],
+    "appearance" => &[Keywords(&[
+      "none",
+      "auto",
+      "base",
+      "menulist-button",
+      "textfield",
+      "button",
+      "checkbox",
+      "listbox",
+      "menulist",
+      "meter",
+      "progress-bar",
+      "push-button",
+      "radio",
+      "searchfield",
+      "slider-horizontal",
+      "square-button",
+      "textarea",
+    ])],
+    "color-scheme" => &[CustomIdent],
+    "scrollbar-gutter" => &[Keywords(&["auto", "stable", "both-edges"])],
+    "touch-action" => &[Keywords(&[
+      "auto",
+      "none",
+      "pan-x",
+      "pan-y",
+      "pan-left",
+      "pan-right",
+      "pan-up",
+      "pan-down",
+      "pinch-zoom",
+      "manipulation",
+    ])],
+    _ => return None,
+  };
+  Some(components)
+}
+
+/// Checks the value of a declaration, returning the range of the first invalid component in the value with an error message.
+pub(crate) fn validate_value(property: &str, value: &str) -> Result<(), (Range<usize>, String)> {
+  let property = property.to_ascii_lowercase();
+  let property = unprefixed(&property);
+  let Some(accepted) = components(property) else {
+    return Ok(());
+  };
+  let tokens = tokenize(value);
+  // the value is only known once it is substituted.
+  if tokens.iter().any(|token| {
+    token.kind == TokenKind::Function
+      && SUBSTITUTION_FUNCTIONS.contains(&function_name(value, token).as_str())
+  }) {
+    return Ok(());
+  }
+
+  let mut depth = 0;
+  for token in tokens.iter() {
+    let text = &value[token.range.clone()];
+    if depth > 0 {
+      match token.kind {
+        TokenKind::Function | TokenKind::OpenParen => depth += 1,
+        TokenKind::CloseParen => depth -= 1,
+        // units inside of functions are checked too, e.g. `calc(1pxx + 2px)`.
+        TokenKind::Dimension if unit_components(&dimension_unit(text)).is_none() => {
+          return Err((token.range.clone(), unknown_unit(text, accepted)));
+        }
+        _ => {}
+      }
+      continue;
+    }
+    let valid = match token.kind {
+      TokenKind::Whitespace
+      | TokenKind::Comma
+      | TokenKind::Delim(_)
+      | TokenKind::OpenParen
+      | TokenKind::CloseParen => true,
+      TokenKind::Ident => {
+        let ident = text.to_ascii_lowercase();
+        CSS_WIDE_KEYWORDS.contains(&ident.as_str())
+          // vendor specific keywords, e.g. `-webkit-box`, and dashed idents.
+          || ident.starts_with('-')
+          || accepted.iter().any(|component| match component {
+            Keywords(keywords) => keywords.contains(&ident.as_str()),
+            Color => NAMED_COLORS.contains(&ident.as_str()),
+            CustomIdent => true,
+            _ => false,
+          })
+      }
+      TokenKind::Number => {
+        let zero = text
+          .trim_start_matches(['+', '-'])
+          .trim_matches(['0', '.'])
+          .is_empty();
+        accepted.iter().any(|component| match component {
+          Number => true,
+          Integer => !text.contains(['.', 'e', 'E']),
+          Length => zero,
+          _ => false,
+        })
+      }
+      TokenKind::Percentage => accepted.contains(&Percentage),
+      TokenKind::Dimension => match unit_components(&dimension_unit(text)) {
+        Some(components) => components
+          .iter()
+          .any(|component| accepted.contains(component)),
+        None => return Err((token.range.clone(), unknown_unit(text, accepted))),
+      },
+      TokenKind::Hash if accepted.contains(&Color) => {
+        let hex = &text[1..];
+        if !matches!(hex.len(), 3 | 4 | 6 | 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+          return Err((token.range.clone(), format!("Invalid hex color `{}`", text)));
+        }
+        true
+      }
+      TokenKind::String => accepted.contains(&Quoted),
+      TokenKind::Url => accepted.contains(&Image),
+      TokenKind::Function => {
+        depth += 1;
+        let name = function_name(value, token);
+        if COLOR_FUNCTIONS.contains(&name.as_str()) {
+          accepted.contains(&Color)
+        } else if IMAGE_FUNCTIONS.contains(&name.as_str()) || name == "url" {
+          accepted.contains(&Image)
+        } else if MATH_FUNCTIONS.contains(&name.as_str()) {
+          accepted.iter().any(|component| {
+            matches!(
+              component,
+              Length | Percentage | Number | Integer | Angle | Time | Resolution | Flex
+            )
+          })
+        } else {
+          // other functions like `cubic-bezier()` aren't checked.
+          true
+        }
+      }
+      _ => true,
+    };
+    if !valid {
+      return Err((
+        token.range.clone(),
+        invalid_component(text, property, accepted),
+      ));
+    }
+  }
+  Ok(())
+}
+
+/// Whether every identifier of the property's value which is a color name is a color,
+/// e.g. for `color` or `border` but not for `grid-area`, whose names could be colors.
+pub(crate) fn names_colors(property: &str) -> bool {
+  components(unprefixed(property))
+    .is_some_and(|components| components.contains(&Color) && !components.contains(&CustomIdent))
+}
+
+// `-webkit-box-shadow` is checked as `box-shadow`.
+fn unprefixed(property: &str) -> &str {
+  ["-webkit-", "-moz-", "-ms-", "-o-"]
+    .iter()
+    .find_map(|prefix| property.strip_prefix(prefix))
+    .unwrap_or(property)
+}
+
+fn function_name(value: &str, token: &Token) -> String {
+  value[token.range.clone()]
+    .trim_end_matches('(')
+    .to_ascii_lowercase()
+}
+
+// the unit of a dimension, e.g. `px` of `-1.5e3px`.
+fn dimension_unit(dimension: &str) -> String {
+  let bytes = dimension.as_bytes();
+  let digits = |mut end: usize| {
+    while bytes
+      .get(end)
+      .is_some_and(|byte| byte.is_ascii_digit() || *byte == b'.')
+    {
+      end += 1;
+    }
+    end
+  };
+  let mut end = digits(usize::from(matches!(bytes.first(), Some(b'+' | b'-'))));
+  // an exponent, unless it is the start of a unit like `em`.
+  if matches!(bytes.get(end), Some(b'e' | b'E')) {
+    let exponent = end + 1 + usize::from(matches!(bytes.get(end + 1), Some(b'+' | b'-')));
+    if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+      end = digits(exponent);
+    }
+  }
+  dimension[end..].to_ascii_lowercase()
+}
+
+fn unit_components(unit: &str) -> Option<&'static [Component]> {
+  if LENGTH_UNITS.contains(&unit) {
+    Some(&[Length])
+  } else if ANGLE_UNITS.contains(&unit) {
+    Some(&[Angle])
+  } else if TIME_UNITS.contains(&unit) {
+    Some(&[Time])
+  } else if RESOLUTION_UNITS.contains(&unit) {
+    Some(&[Resolution])
+  } else if FLEX_UNITS.contains(&unit) {
+    Some(&[Flex])
+  } else {
+    None
+  }
+}
+
+fn unknown_unit(dimension: &str, accepted: &[Component]) -> String {
+  let unit = dimension_unit(dimension);
+  // units of the property first, e.g. `px` rather than `s` for `width: 10pxx`.
+  let units = accepted
+    .iter()
+    .flat_map(|component| match component {
+      Length => LENGTH_UNITS,
+      Angle => ANGLE_UNITS,
+      Time => TIME_UNITS,
+      Resolution => RESOLUTION_UNITS,
+      Flex => FLEX_UNITS,
+      _ => &[],
+    })
+    .copied()
+    .collect::<Vec<_>>();
+  let units = if units.is_empty() {
+    [
+      LENGTH_UNITS,
+      ANGLE_UNITS,
+      TIME_UNITS,
+      RESOLUTION_UNITS,
+      FLEX_UNITS,
+    ]
+    .concat()
+  } else {
+    units
+  };
+  match closest(&unit, units) {
+    Some(suggestion) => format!("Unknown unit `{}`, did you mean {}", unit, suggestion),
+    None => format!("Unknown unit `{}`", unit),
+  }
+}
+
+fn invalid_component(text: &str, property: &str, accepted: &[Component]) -> String {
+  let is_ident = text.starts_with(|c: char| c.is_ascii_alphabetic());
+  let candidates = accepted
+    .iter()
+    .flat_map(|component| match component {
+      Keywords(keywords) => keywords,
+      Color => NAMED_COLORS,
+      _ => &[],
+    })
+    .copied();
+  match closest(&text.to_ascii_lowercase(), candidates) {
+    Some(suggestion) if is_ident => format!(
+      "Unknown value `{}` for {}, did you mean {}",
+      text, property, suggestion
+    ),
+    _ if accepted.contains(&Length) && text.parse::<f64>().is_ok() => {
+      format!("Missing unit after `{}` for {}", text, property)
+    }
+    _ => format!("`{}` isn't a valid value of {}", text, property),
+  }
+}
+
+// the closest candidate, if it is close enough to be a typo.
+fn closest<'a>(text: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+  candidates
+    .into_iter()
+    .map(|candidate| (levenshtein(text, candidate), candidate))
+    // ties go to the candidates starting like the text, e.g. `rem` rather than `em` for `rm`.
+    .min_by_key(|(distance, candidate)| (*distance, candidate.get(..1) != text.get(..1)))
+    .filter(|(distance, _)| *distance <= 2.max(text.len() / 3))
+    .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn error(property: &str, value: &str) -> Option<(String, String)> {
+    validate_value(property, value)
+      .err()
+      .map(|(range, message)| (value[range].to_string(), message))
+  }
+
+  #[test]
+  fn accepts_valid_values() {
+    for (property, value) in [
+      ("color", "red"),
+      ("color", "#1e90ff"),
+      ("color", "rgb(0 0 0 / 50%)"),
+      ("color", "CurrentColor"),
+      ("background-color", "var(--bg, redd)"),
+      ("width", "calc(100% - 2rem)"),
+      ("width", "-webkit-fill-available"),
+      ("margin", "0 auto"),
+      ("padding", "1px 2em 3% 0"),
+      ("border", "1px solid rgba(0, 0, 0, 0.1)"),
+      ("line-height", "1.5"),
+      ("z-index", "10"),
+      ("transition-duration", "200ms, 1s"),
+      ("display", "inherit"),
+      ("font-family", "\"Fira Sans\", sans-serif"),
+      ("transform", "rotate(10deg)"),
+      ("-webkit-box-shadow", "inset 0 1px 2px #000"),
+      ("grid-auto-rows", "minmax(100px, auto) 1fr"),
+    ] {
+      assert_eq!(error(property, value), None, "{}: {}", property, value);
+    }
+  }
+
+  #[test]
+  fn reports_invalid_values() {
+    let errors = [
+      ("color", "redd"),
+      ("width", "10pxx"),
+      ("width", "calc(100% - 1rm)"),
+      ("display", "flexx"),
+      ("color", "#ggg"),
+      ("margin", "10"),
+      ("opacity", "1px"),
+    ]
+    .map(|(property, value)| error(property, value).unwrap());
+    assert_eq!(
+      errors,
+      [
+        ("redd", "Unknown value `redd` for color, did you mean red"),
+        ("10pxx", "Unknown unit `pxx`, did you mean px"),
+        ("1rm", "Unknown unit `rm`, did you mean rem"),
+        (
+          "flexx",
+          "Unknown value `flexx` for display, did you mean flex"
+        ),
+        ("#ggg", "Invalid hex color `#ggg`"),
+        ("10", "Missing unit after `10` for margin"),
+        ("1px", "`1px` isn't a valid value of opacity"),
+      ]
+      .map(|(text, message)| (text.to_string(), message.to_string()))
+    );
+  }
+}
@@ -1,22 +1,300 @@
-mod css_at_rule;
-mod css_style_declar;
-mod css_style_rule;
-mod css_style_sheet;
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
-use crate::{
-  Class,
-  style::{Rule, StyleSheet},
-};
+use crate::parser::{self, ParseOptions, Severity};
+use crate::style::{ScopeOptions, StyleSheet};
+use crate::{Class, Diagnostic, Indent, Targets};
+
+/// Parses the content of a css file without scoping it.
+/// Every invalid part of the css is reported with its line and column.
+/// Unknown properties and invalid values are only warned about, see [`StyleSheet::warnings_from_str`].
+impl FromStr for StyleSheet {
+  type Err = Vec<Diagnostic>;
+
+  fn from_str(style_str: &str) -> Result<Self, Self::Err> {
+    let (style_sheet, errors) = parse_str(style_str, Severity::Error);
+    if errors.is_empty() {
+      return Ok(style_sheet);
+    }
+    Err(errors)
+  }
+}
+
+impl StyleSheet {
+  /// Css of a css file which is likely a mistake, e.g. an unknown property, an invalid value like `color: redd`
+  /// or `-webkit-transform` without the standard `transform` property.
+  /// Each warning is located by its line and column in the file.
+  pub fn warnings_from_str(style_str: &str) -> Vec<Diagnostic> {
+    parse_str(style_str, Severity::Warning).1
+  }
+}
+
+// parses the css with validation, keeping the reports of the given severity.
+fn parse_str(style_str: &str, severity: Severity) -> (StyleSheet, Vec<Diagnostic>) {
+  let (style_sheet, errors) = parser::parse(
+    style_str,
+    ParseOptions {
+      validate_properties: true,
+      validation_warnings: true,
+    },
+  );
+  let errors = errors
+    .into_iter()
+    .filter(|error| error.severity == severity)
+    .map(|error| Diagnostic::at(style_str, error.range.start, error.message))
+    .collect();
+  (style_sheet, errors)
+}
 
 /// This function will build the whole style text as the String.
 /// This build_style is string version of the build_style method from style macro.
-pub fn build_style_from_str(style_str: &str, class: &Class) -> String {
-  let mut style = String::new();
-  let style_sheet = StyleSheet::from_str(style_str, class);
-  style_sheet.rules.iter().for_each(|rule| match rule {
-    Rule::AtRule(at_rule) => style.push_str(&at_rule.css_text()),
-    Rule::StyleRule(style_rule) => style.push_str(&style_rule.css_text()),
-  });
-
-  style
+pub fn build_style_from_str(
+  style_str: &str,
+  class: &Class,
+  options: &ScopeOptions,
+) -> Result<String, Vec<Diagnostic>> {
+  let mut style_sheet = style_str.parse::<StyleSheet>()?;
+  style_sheet.scope(class, options);
+  Ok(style_sheet.to_string())
+}
+
+/// String version of [`crate::module_from_ts`], scopes the style sheet as a css module.
+pub fn build_module_from_str(
+  style_str: &str,
+  class: &Class,
+  options: &ScopeOptions,
+) -> Result<(String, BTreeMap<String, String>), Vec<Diagnostic>> {
+  let mut style_sheet = style_str.parse::<StyleSheet>()?;
+  let names = style_sheet.scope_module(class, options);
+  Ok((style_sheet.to_string(), names))
+}
+
+/// Warnings about the css of a css file, see [`StyleSheet::warnings_from_str`].
+/// Invalid css is reported by [`build_style_from_str`] instead.
+pub fn build_warnings_from_str(style_str: &str) -> Vec<Diagnostic> {
+  StyleSheet::warnings_from_str(style_str)
+}
+
+/// Adds the vendor prefixes the targeted browsers need to css built by this crate, see [`StyleSheet::prefix`].
+/// Css which doesn't parse is reported with its diagnostics instead of being returned without prefixes.
+pub fn prefix_css(css: &str, targets: &Targets) -> Result<String, Vec<Diagnostic>> {
+  if targets.is_empty() {
+    return Ok(css.to_string());
+  }
+  let mut style_sheet = css.parse::<StyleSheet>()?;
+  style_sheet.prefix(targets);
+  Ok(style_sheet.to_string())
+}
+
+/// Pretty prints css built by this crate, see [`StyleSheet::to_pretty_string`].
+/// Css which doesn't parse is reported with its diagnostics instead of being returned as it is.
+pub fn pretty_css(css: &str, indent: Indent) -> Result<String, Vec<Diagnostic>> {
+  Ok(css.parse::<StyleSheet>()?.to_pretty_string(indent))
+}
+
+/// Minifies css built by this crate, see [`StyleSheet::minify`].
+/// Css which doesn't parse is reported with its diagnostics instead of being returned as it is.
+pub fn minify_css(css: &str) -> Result<String, Vec<Diagnostic>> {
+  let mut style_sheet = css.parse::<StyleSheet>()?;
+  style_sheet.minify();
+  Ok(style_sheet.to_minified_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::style::{AtRuleBlock, Rule};
+
+  #[test]
+  fn inspect_ast() {
+    let style_sheet: StyleSheet = "@media screen and (min-width: 900px) {\n  .one, #two { color: red !important; margin: 0 }\n}"
+      .parse()
+      .unwrap();
+    let [Rule::AtRule(at_rule)] = style_sheet.rules.as_slice() else {
+      panic!("Expected a single at-rule");
+    };
+    assert_eq!(at_rule.name, "media");
+    assert_eq!(at_rule.prelude, "screen and (min-width: 900px)");
+    let Some(AtRuleBlock::Rules(rules)) = &at_rule.block else {
+      panic!("Expected nested rules");
+    };
+    let [Rule::StyleRule(style_rule)] = rules.as_slice() else {
+      panic!("Expected a single style rule");
+    };
+    assert_eq!(style_rule.selectors.0.len(), 2);
+    let declarations = &style_rule.style.declarations;
+    assert_eq!(declarations[0].property, "color");
+    assert_eq!(declarations[0].value, "red");
+    assert!(declarations[0].important);
+    assert_eq!(declarations[1].property, "margin");
+    assert!(!declarations[1].important);
+    assert_eq!(
+      style_sheet.to_string(),
+      "@media screen and (min-width: 900px){.one,#two{color: red !important;margin: 0;}}"
+    );
+  }
+
+  #[test]
+  fn scope_ast() {
+    let mut style_sheet: StyleSheet = "div :deep(h3), *{color: red;}".parse().unwrap();
+    style_sheet.scope(&Class::new("test".into()), &ScopeOptions::default());
+    assert_eq!(style_sheet.to_string(), "div.test h3,.test{color: red;}");
+  }
+
+  #[test]
+  fn scope_custom_properties() {
+    let css = "@property --angle { syntax: '<angle>'; inherits: false; initial-value: 0deg; }\n.card { --gap: 1px; --theme-bg: red; margin: var(--gap) calc(var(--gap) * 2); background: var(--theme-bg, var(--page-bg)); }";
+    let class = Class::new("test".into());
+    let options = ScopeOptions {
+      scope_custom_properties: true,
+      public_custom_properties: Some("--theme-".into()),
+      ..Default::default()
+    };
+    assert_eq!(
+      build_style_from_str(css, &class, &options).unwrap(),
+      "@property --angle-test{syntax: '<angle>';inherits: false;initial-value: 0deg;}.card.test{--gap-test: 1px;--theme-bg: red;margin: var(--gap-test) calc(var(--gap-test) * 2);background: var(--theme-bg, var(--page-bg));}"
+    );
+    assert_eq!(
+      build_style_from_str(css, &class, &ScopeOptions::default()).unwrap(),
+      "@property --angle{syntax: '<angle>';inherits: false;initial-value: 0deg;}.card.test{--gap: 1px;--theme-bg: red;margin: var(--gap) calc(var(--gap) * 2);background: var(--theme-bg, var(--page-bg));}"
+    );
+  }
+
+  #[test]
+  fn scope_module() {
+    let css = ".btn:hover, .btn.primary :global(.icon) { color: red; }\nbody.dark .btn:not(.active) { animation: spin 1s; }\n@keyframes spin { to { rotate: 1turn; } }";
+    let (style, names) =
+      build_module_from_str(css, &Class::new("test".into()), &ScopeOptions::default()).unwrap();
+    assert_eq!(
+      style,
+      ".btn-test:hover,.btn-test.primary-test .icon{color: red;}body.dark .btn-test:not(.active-test){animation: spin-test 1s;}@keyframes spin-test{to{rotate: 1turn;}}"
+    );
+    assert_eq!(
+      names.into_iter().collect::<Vec<_>>(),
+      vec![
+        ("active".to_string(), "active-test".to_string()),
+        ("btn".to_string(), "btn-test".to_string()),
+        ("primary".to_string(), "primary-test".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn reports_diagnostics() {
+    let css = "/* comment */\np {\n  color: red\n  margin: 0;\n}\n}\n.é { top: 0 }\ndiv";
+    let diagnostics = css.parse::<StyleSheet>().unwrap_err();
+    assert_eq!(
+      diagnostics
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+      vec![
+        "4:3: Missing semicolon before `margin`",
+        "6:1: Unexpected `}`",
+        "8:1: Expected a `{` block after the selector",
+      ]
+    );
+    assert!(
+      build_style_from_str(css, &Class::new("test".into()), &ScopeOptions::default()).is_err()
+    );
+  }
+
+  #[test]
+  fn warns_about_invalid_properties_and_values() {
+    let css = "p {\n  color: redd;\n  colr: red;\n}\n.a { margin: 0 }";
+    assert!(css.parse::<StyleSheet>().is_ok());
+    assert_eq!(
+      StyleSheet::warnings_from_str(css)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+      vec![
+        "2:10: Unknown value `redd` for color, did you mean red",
+        "3:3: Did you mean to use color property",
+      ]
+    );
+  }
+
+  #[test]
+  fn rejects_empty_values() {
+    let diagnostics = ".a { color: ; --gap: ; margin: !important }"
+      .parse::<StyleSheet>()
+      .unwrap_err();
+    assert_eq!(
+      diagnostics
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+      vec![
+        "1:11: Expected a value for `color`",
+        "1:30: Expected a value for `margin`",
+      ]
+    );
+  }
+
+  #[test]
+  fn warns_about_prefixed_properties() {
+    let css = "p { -webkit-transform: none; -moz-appearance: none; appearance: none; }\n@media print {\n  div { -webkit-box-orient: vertical; -webkit-user-select: none; }\n}";
+    assert_eq!(
+      StyleSheet::warnings_from_str(css)
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+      vec![
+        "1:5: `-webkit-transform` is used without the standard `transform` property",
+        "3:39: `-webkit-user-select` is used without the standard `user-select` property",
+      ]
+    );
+  }
+
+  #[test]
+  fn reports_css_which_does_not_parse() {
+    let css = "p{user-select: none;}}";
+    let targets = "firefox >= 50".parse().unwrap();
+    assert_eq!(
+      prefix_css(css, &targets)
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+      vec!["1:22: Unexpected `}`"]
+    );
+    assert_eq!(prefix_css(css, &Targets::default()).unwrap(), css);
+    assert!(minify_css(css).is_err());
+    assert!(pretty_css(css, Indent::default()).is_err());
+  }
+
+  #[test]
+  fn reports_invalid_selectors() {
+    let css = "div >{}\n.a ++ .b { color: red }\n  .a, {}\n#{}\n!!!{}\np { margin: 0 }";
+    assert_eq!(
+      css
+        .parse::<StyleSheet>()
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+      vec![
+        "1:1: Expected a selector after `>`",
+        "2:1: Expected a selector after `+`",
+        "3:3: Expected a selector after `,`",
+        "4:1: Expected a name after `#`",
+        "5:1: Unexpected `!` in the selector",
+      ]
+    );
+  }
+
+  #[test]
+  fn rejects_names_joined_with_nesting() {
+    let diagnostics = ".a {\n  &-x { color: red }\n}"
+      .parse::<StyleSheet>()
+      .unwrap_err();
+    assert_eq!(
+      diagnostics
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+      vec!["2:3: `&-x` isn't valid css nesting, `&` can't be joined with a name"]
+    );
+  }
 }
@@ -0,0 +1,365 @@
+//! Browsers the generated css has to work in, selected with browserslist style queries,
+//! e.g. `last 2 versions, firefox esr, not dead` or `chrome >= 90, safari >= 14`.
+//! Like the class naming, they are read from the `[package.metadata.stylers]` table of the crate's Cargo.toml
+//! and from an environment variable, which takes precedence.
+//!
+//! ```toml
+//! [package.metadata.stylers]
+//! targets = "defaults"
+//! ```
+//!
+//! Usage statistics aren't vendored, so queries like `> 0.5%` aren't supported
+//! and `defaults` stands for `last 2 versions, firefox esr, not dead`.
+//! Without targets nothing is prefixed.
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::naming::{invalid_config, manifest_config};
+
+pub const TARGETS_ENV: &str = "STYLERS_TARGETS";
+
+/// Version of a browser release, e.g. `16.4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+  pub major: u16,
+  pub minor: u16,
+}
+
+impl Version {
+  pub const fn new(major: u16, minor: u16) -> Self {
+    Self { major, minor }
+  }
+}
+
+impl FromStr for Version {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (major, minor) = s.split_once('.').unwrap_or((s, "0"));
+    match (major.parse(), minor.parse()) {
+      (Ok(major), Ok(minor)) => Ok(Self::new(major, minor)),
+      _ => Err(format!("Invalid browser version {:?}", s)),
+    }
+  }
+}
+
+impl fmt::Display for Version {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.major)?;
+    if self.minor != 0 {
+      write!(f, ".{}", self.minor)?;
+    }
+    Ok(())
+  }
+}
+
+struct BrowserData {
+  /// browserslist name, e.g. `ios_saf`.
+  name: &'static str,
+  /// Released versions, oldest first.
+  versions: &'static [Version],
+  /// Extended support releases, e.g. of firefox.
+  esr: &'static [Version],
+  /// browserslist considers this version and the older ones dead.
+  dead_until: Option<Version>,
+}
+
+/// Variant of a css feature which some browsers need instead of the standard one,
+/// e.g. `-webkit-` for the `user-select` property or `::-moz-selection` for the `::selection` selector.
+pub(crate) struct Variant {
+  pub(crate) feature: &'static str,
+  pub(crate) variant: &'static str,
+  /// Browsers which shipped the variant, with the version supporting the standard feature,
+  /// `None` if none does.
+  standard_since: &'static [(&'static str, Option<Version>)],
+}
+
+include!(concat!(env!("OUT_DIR"), "/compat.rs"));
+
+/// Variants of the property, e.g. `-moz-` and `-webkit-` for `appearance`.
+pub(crate) fn property_variants(property: &str) -> &'static [Variant] {
+  variants(&PROPERTY_VARIANTS, property)
+}
+
+/// Variants of the pseudo-class or pseudo-element, e.g. `::-moz-selection` for `::selection`.
+pub(crate) fn selector_variants(selector: &str) -> &'static [Variant] {
+  variants(&SELECTOR_VARIANTS, selector)
+}
+
+/// Variants of the at-rule, e.g. `-webkit-` for `keyframes`.
+pub(crate) fn at_rule_variants(name: &str) -> &'static [Variant] {
+  variants(&AT_RULE_VARIANTS, name)
+}
+
+/// Standard pseudo-classes and pseudo-elements which have variants, e.g. `::placeholder`.
+pub(crate) fn prefixed_selectors() -> impl Iterator<Item = &'static str> {
+  SELECTOR_VARIANTS
+    .chunk_by(|a, b| a.feature == b.feature)
+    .map(|variants| variants[0].feature)
+}
+
+fn variants(table: &'static [Variant], feature: &str) -> &'static [Variant] {
+  let start = table.partition_point(|variant| variant.feature < feature);
+  let end = table.partition_point(|variant| variant.feature <= feature);
+  &table[start..end]
+}
+
+/// Oldest targeted version of every targeted browser, which is all prefixing needs to know.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Targets {
+  oldest: BTreeMap<&'static str, Version>,
+}
+
+impl Targets {
+  /// Loads the targets of the crate in `manifest_dir`, or none if there are none configured.
+  /// The environment variable overrides `targets` in `[package.metadata.stylers]`.
+  pub fn load(manifest_dir: Option<&Path>) -> Result<Self, String> {
+    if let Ok(query) = std::env::var(TARGETS_ENV) {
+      return query.parse();
+    }
+    let Some(manifest_dir) = manifest_dir else {
+      return Ok(Self::default());
+    };
+    let manifest_path = manifest_dir.join("Cargo.toml");
+    let Some(targets) =
+      manifest_config(&manifest_path)?.and_then(|config| config.get("targets").cloned())
+    else {
+      return Ok(Self::default());
+    };
+    let invalid = || {
+      invalid_config(
+        &manifest_path,
+        "targets",
+        "a query string or an array of them",
+      )
+    };
+    match targets {
+      toml::Value::String(query) => query.parse(),
+      toml::Value::Array(queries) => queries
+        .iter()
+        .map(|query| query.as_str().ok_or_else(invalid))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(", ")
+        .parse(),
+      _ => Err(invalid()),
+    }
+  }
+
+  /// Loads the targets of the crate currently being compiled, using `CARGO_MANIFEST_DIR`.
+  pub fn from_cargo_env() -> Result<Self, String> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    Self::load(manifest_dir.as_deref())
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.oldest.is_empty()
+  }
+
+  /// Oldest targeted version of the browser, e.g. `Some(14.0)` for `safari` with `safari >= 14`.
+  pub fn oldest(&self, browser: &str) -> Option<Version> {
+    self.oldest.get(browser).copied()
+  }
+
+  /// Whether any targeted browser needs the variant.
+  pub(crate) fn needs(&self, variant: &Variant) -> bool {
+    variant.standard_since.iter().any(|(browser, since)| {
+      self
+        .oldest(browser)
+        .is_some_and(|oldest| since.is_none_or(|since| oldest < since))
+    })
+  }
+}
+
+/// Parses a browserslist style query. Queries are separated by `,` or `or`,
+/// and `not` removes the releases of its query from the ones selected before it.
+impl FromStr for Targets {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut releases = BTreeSet::new();
+    let query = s.to_ascii_lowercase();
+    for query in query
+      .split(',')
+      .flat_map(|query| query.split(" or "))
+      .map(str::trim)
+      .filter(|query| !query.is_empty())
+    {
+      match query.strip_prefix("not ") {
+        Some(query) => {
+          let excluded = select(query.trim())?;
+          releases.retain(|release| !excluded.contains(release));
+        }
+        None => releases.extend(select(query)?),
+      }
+    }
+    let mut oldest = BTreeMap::new();
+    for (browser, version) in releases {
+      oldest.entry(browser).or_insert(version);
+    }
+    Ok(Self { oldest })
+  }
+}
+
+type Release = (&'static str, Version);
+
+// releases selected by a single query, e.g. `safari >= 14`.
+fn select(query: &str) -> Result<BTreeSet<Release>, String> {
+  let words = query.split_whitespace().collect::<Vec<_>>();
+  let releases = |browser: &'static BrowserData| {
+    browser
+      .versions
+      .iter()
+      .map(move |version| (browser.name, *version))
+  };
+  let last = |count: &str, browser: &'static BrowserData| {
+    let count = count
+      .parse::<usize>()
+      .map_err(|_| format!("Expected a number of versions in `{}`", query))?;
+    Ok::<_, String>(releases(browser).rev().take(count))
+  };
+  let selected = match words.as_slice() {
+    ["defaults"] => {
+      let mut selected = select("last 2 versions")?;
+      selected.extend(select("firefox esr")?);
+      let dead = select("dead")?;
+      selected.retain(|release| !dead.contains(release));
+      selected
+    }
+    ["dead"] => BROWSERS
+      .iter()
+      .flat_map(|browser| {
+        releases(browser)
+          .filter(|(_, version)| browser.dead_until.is_some_and(|dead| *version <= dead))
+      })
+      .collect(),
+    ["last", count, "versions"] => {
+      let mut selected = BTreeSet::new();
+      for browser in BROWSERS.iter() {
+        selected.extend(last(count, browser)?);
+      }
+      selected
+    }
+    ["last", count, browser, "versions"] => last(count, browser_data(browser, query)?)?.collect(),
+    [browser, "esr"] => {
+      let browser = browser_data(browser, query)?;
+      if browser.esr.is_empty() {
+        return Err(format!("{} has no extended support releases", browser.name));
+      }
+      browser
+        .esr
+        .iter()
+        .map(|version| (browser.name, *version))
+        .collect()
+    }
+    [browser, operator @ (">" | ">=" | "<" | "<="), version] => {
+      let version = version.parse::<Version>()?;
+      releases(browser_data(browser, query)?)
+        .filter(|(_, release)| match *operator {
+          ">" => *release > version,
+          ">=" => *release >= version,
+          "<" => *release < version,
+          _ => *release <= version,
+        })
+        .collect()
+    }
+    [browser, versions] => {
+      let browser = browser_data(browser, query)?;
+      let (from, to) = versions.split_once('-').unwrap_or((versions, versions));
+      let (from, to) = (from.parse::<Version>()?, to.parse::<Version>()?);
+      let selected = releases(browser)
+        .filter(|(_, version)| (from..=to).contains(version))
+        .collect::<BTreeSet<_>>();
+      if selected.is_empty() {
+        return Err(format!("Unknown version {} of {}", versions, browser.name));
+      }
+      selected
+    }
+    _ => {
+      return Err(format!(
+        "Unsupported browser query `{}`, expected e.g. `last 2 versions`, `firefox esr`, `not dead` or `safari >= 14`",
+        query
+      ));
+    }
+  };
+  Ok(selected)
+}
+
+fn browser_data(name: &str, query: &str) -> Result<&'static BrowserData, String> {
+  let name = match name {
+    "ff" | "fx" => "firefox",
+    "ios" => "ios_saf",
+    "chromeandroid" => "and_chr",
+    "explorer" => "ie",
+    name => name,
+  };
+  BROWSERS
+    .iter()
+    .find(|browser| browser.name == name)
+    .ok_or_else(|| format!("Unknown browser `{}` in `{}`", name, query))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn selects_oldest_targeted_versions() {
+    let targets: Targets =
+      "Chrome >= 90, safari 14-15.4, ios 16.4, not chrome < 100, last 1 firefox versions"
+        .parse()
+        .unwrap();
+    assert_eq!(targets.oldest("chrome"), Some(Version::new(100, 0)));
+    assert_eq!(targets.oldest("safari"), Some(Version::new(14, 0)));
+    assert_eq!(targets.oldest("ios_saf"), Some(Version::new(16, 4)));
+    assert_eq!(targets.oldest("firefox"), Some(Version::new(131, 0)));
+    assert_eq!(targets.oldest("ie"), None);
+
+    let defaults: Targets = "defaults".parse().unwrap();
+    assert_eq!(defaults.oldest("firefox"), Some(Version::new(115, 0)));
+    assert_eq!(defaults.oldest("ie"), None);
+    assert_eq!(defaults.oldest("edge"), Some(Version::new(129, 0)));
+
+    assert!("".parse::<Targets>().unwrap().is_empty());
+    assert!("> 0.5%".parse::<Targets>().is_err());
+    assert!("netscape 4".parse::<Targets>().is_err());
+  }
+
+  #[test]
+  fn needs_variants_of_older_versions() {
+    let needs = |query: &str, variants: &[Variant]| {
+      let targets = query.parse::<Targets>().unwrap();
+      variants
+        .iter()
+        .filter(|variant| targets.needs(variant))
+        .map(|variant| variant.variant)
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      needs("safari >= 17", property_variants("user-select")),
+      ["-webkit-"]
+    );
+    assert_eq!(
+      needs(
+        "chrome >= 90, firefox >= 60",
+        property_variants("user-select")
+      ),
+      ["-moz-"]
+    );
+    assert!(needs("chrome >= 90", property_variants("transform")).is_empty());
+    assert_eq!(
+      needs("firefox 50", selector_variants("::placeholder")),
+      ["::-moz-placeholder"]
+    );
+    // every prefixed property is known to the validation.
+    for variant in PROPERTY_VARIANTS.iter() {
+      assert!(
+        crate::style::properties::prefixes(variant.feature).contains(&variant.variant),
+        "{}{}",
+        variant.variant,
+        variant.feature
+      );
+    }
+  }
+}
@@ -1,108 +1,363 @@
 //! This crate provides style macro for scoped css in rust web frameworks which follows component like architecture e.g Leptos.
-#![feature(proc_macro_span)]
+#![feature(proc_macro_tracked_env, proc_macro_tracked_path)]
 #![warn(clippy::panic, clippy::unwrap_used, clippy::expect_used, clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
 use litrs::StringLit;
-use proc_macro2::{self, TokenStream, TokenTree};
-use quote::quote;
+use proc_macro2::{self, Ident, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
 
-use stylers_core::Class;
-use stylers_core::{from_str, from_ts};
+use stylers_core::{
+  CONFIG_ENV_VARS, Class, ClassNaming, Diagnostic, Indent, ScopeOptions, Targets, path_hint,
+};
+use stylers_core::{
+  from_str, from_ts, module_from_str, module_from_ts, prefix_css, pretty_css, warnings_from_str,
+  warnings_from_ts,
+};
 
 /// style macro take any valid css as input and returns a unique class name.
 /// For examples see: <https://github.com/abishekatp/stylers>
 #[proc_macro]
 pub fn style(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
-  let strval = ts.to_string();
-  let class = Class::rand_class_from_seed(strval);
-  let class = class.as_name();
-  let expanded = quote! {
-      #class
-  };
+  let token_stream = TokenStream::from(ts).into_iter();
+  let expanded = style_(token_stream).unwrap_or_else(|err| err);
   proc_macro::TokenStream::from(expanded)
 }
 
+fn style_(token_stream: impl Iterator<Item = TokenTree>) -> Result<TokenStream, TokenStream> {
+  let token_stream = token_stream.collect::<TokenStream>();
+  let (naming, scope_options) = config()?;
+  let class = Class::from_ts(
+    token_stream.clone().into_iter(),
+    true,
+    &naming,
+    &scope_options,
+    source_hint().as_deref(),
+  )
+  .map_err(css_errors)?;
+
+  Ok(with_css_warnings(token_stream, quote! { #class }))
+}
+
 /// style_sheet macro take css file path as a string input and returns a unique class name.
 /// For examples see: <https://github.com/abishekatp/stylers>
 #[proc_macro]
 pub fn style_sheet(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
-  let file_path = ts.to_string();
-  let file_path = file_path.trim_matches('"');
-  let css_content = std::fs::read_to_string(file_path).expect("Expected to read file");
-  let class = Class::rand_class_from_seed(css_content.to_string());
-  let class = class.as_name();
-  let expanded = quote! {
-      #class
-  };
+  let token_stream = TokenStream::from(ts).into_iter();
+  let expanded = style_sheet_(token_stream).unwrap_or_else(|err| err);
   proc_macro::TokenStream::from(expanded)
 }
 
+fn style_sheet_(token_stream: impl Iterator<Item = TokenTree>) -> Result<TokenStream, TokenStream> {
+  let token_stream = token_stream.collect::<TokenStream>();
+  let span = token_stream
+    .clone()
+    .into_iter()
+    .next()
+    .map_or_else(Span::call_site, |token| token.span());
+  let file_path = token_stream.to_string();
+  let file_path = file_path.trim_matches('"');
+  let css_content = fs::read_to_string(file_path)
+    .map_err(|err| call_site_error(format!("Expected to read file {}: {}", file_path, err)))?;
+
+  let (naming, scope_options) = config()?;
+  let class = Class::from_css(
+    &css_content,
+    &naming,
+    &scope_options,
+    path_hint(Path::new(file_path)),
+  )
+  .map_err(|diagnostics| css_file_errors(file_path, diagnostics, span))?;
+
+  Ok(with_css_file_warnings(
+    file_path,
+    &css_content,
+    span,
+    quote! { #class },
+  ))
+}
+
 #[proc_macro]
 pub fn style_str(token_stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
   let token_stream = TokenStream::from(token_stream).into_iter();
-  let expanded = style_str_(token_stream).unwrap_or_else(|err| quote! { compile_error!(#err) });
+  let expanded = style_str_(token_stream).unwrap_or_else(|err| err);
+  proc_macro::TokenStream::from(expanded)
+}
+
+fn style_str_(token_stream: impl Iterator<Item = TokenTree>) -> Result<TokenStream, TokenStream> {
+  let token_stream = token_stream.collect::<TokenStream>();
+  let (naming, scope_options) = config()?;
+  let class = Class::from_ts(
+    token_stream.clone().into_iter(),
+    true,
+    &naming,
+    &scope_options,
+    source_hint().as_deref(),
+  )
+  .map_err(css_errors)?;
+  let (style, _selectors) = from_ts(
+    token_stream.clone().into_iter(),
+    &class,
+    &scope_options,
+    true,
+  )
+  .map_err(css_errors)?;
+  let style = css_literal(&style)?;
+
+  Ok(with_css_warnings(token_stream, quote! { (#class, #style) }))
+}
+
+/// scoped_style macro is the same as the style macro but returns a `stylers::ScopedStyle`,
+/// which also exposes the class and id names referenced by the scoped selectors.
+#[proc_macro]
+pub fn scoped_style(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let token_stream = TokenStream::from(ts).into_iter();
+  let expanded = scoped_style_(token_stream, false).unwrap_or_else(|err| err);
+  proc_macro::TokenStream::from(expanded)
+}
+
+/// scoped_style_str macro is the same as the scoped_style macro but the returned `stylers::ScopedStyle` includes the css.
+#[proc_macro]
+pub fn scoped_style_str(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let token_stream = TokenStream::from(ts).into_iter();
+  let expanded = scoped_style_(token_stream, true).unwrap_or_else(|err| err);
   proc_macro::TokenStream::from(expanded)
 }
 
-fn style_str_(token_stream: impl Iterator<Item = TokenTree>) -> Result<TokenStream, String> {
-  let class = Class::random();
-  let (style, _selectors) = from_ts(token_stream, &class, true);
+fn scoped_style_(
+  token_stream: impl Iterator<Item = TokenTree>,
+  with_css: bool,
+) -> Result<TokenStream, TokenStream> {
+  let token_stream = token_stream.collect::<TokenStream>();
+  let (naming, scope_options) = config()?;
+  let class = Class::from_ts(
+    token_stream.clone().into_iter(),
+    true,
+    &naming,
+    &scope_options,
+    source_hint().as_deref(),
+  )
+  .map_err(css_errors)?;
+  let (style, sel_map) = from_ts(
+    token_stream.clone().into_iter(),
+    &class,
+    &scope_options,
+    true,
+  )
+  .map_err(css_errors)?;
 
-  Ok(quote! { (#class, #style) })
+  let css = if with_css {
+    let style = css_literal(&style)?;
+    quote! { ::core::option::Option::Some(#style) }
+  } else {
+    quote! { ::core::option::Option::None }
+  };
+  // BTreeSet iterates in order, as needed for the binary search of ScopedStyle.
+  let classes = sel_map.classes.iter();
+  let ids = sel_map.ids.iter();
+  Ok(with_css_warnings(
+    token_stream,
+    quote! {
+      ::stylers::ScopedStyle::new(#class, #css, &[#(#classes),*], &[#(#ids),*])
+    },
+  ))
+}
+
+/// css_module macro takes either inline css or a css file path as a string input.
+/// Every class name of the css is suffixed with a unique class instead of adding a class to the selectors,
+/// and the macro returns a struct with one `&'static str` field per class name, e.g. `styles.primary`.
+/// Dashes in the class names become underscores, e.g. `.icon-left` is `styles.icon_left`.
+/// Referring to a class name which isn't in the css is a compile error.
+/// For examples see: <https://github.com/abishekatp/stylers>
+#[proc_macro]
+pub fn css_module(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let token_stream = TokenStream::from(ts).into_iter();
+  let expanded = css_module_(token_stream).unwrap_or_else(|err| err);
+  proc_macro::TokenStream::from(expanded)
+}
+
+fn css_module_(token_stream: impl Iterator<Item = TokenTree>) -> Result<TokenStream, TokenStream> {
+  let tokens = token_stream.collect::<Vec<_>>();
+  let (naming, scope_options) = config()?;
+  // the tokens of inline css or the css file, which may have warnings.
+  let mut inline_css = None;
+  let mut css_file = None;
+  let names = if let [TokenTree::Literal(path_literal)] = tokens.as_slice()
+    && let Ok(path) = StringLit::try_from(path_literal)
+  {
+    let path = path.value();
+    let css_content = fs::read_to_string(path)
+      .map_err(|err| call_site_error(format!("Expected to read file {}: {}", path, err)))?;
+    let file_errors = |diagnostics| css_file_errors(path, diagnostics, path_literal.span());
+
+    let class = Class::from_module_css(
+      &css_content,
+      &naming,
+      &scope_options,
+      path_hint(Path::new(path)),
+    )
+    .map_err(file_errors)?;
+    let (_style, names) =
+      module_from_str(&css_content, &class, &scope_options).map_err(file_errors)?;
+    css_file = Some((path.to_string(), css_content, path_literal.span()));
+    names
+  } else {
+    let class = Class::from_module_ts(
+      tokens.clone().into_iter(),
+      true,
+      &naming,
+      &scope_options,
+      source_hint().as_deref(),
+    )
+    .map_err(css_errors)?;
+    let (_style, names) = module_from_ts(tokens.clone().into_iter(), &class, &scope_options, true)
+      .map_err(css_errors)?;
+    inline_css = Some(tokens.into_iter().collect());
+    names
+  };
+
+  // field name to its identifier, the class name it comes from and the suffixed name.
+  let mut fields = BTreeMap::new();
+  for (name, renamed) in names.iter() {
+    let field = field_ident(&field_name(name));
+    if let Some((_, other, _)) = fields.insert(field.to_string(), (field.clone(), name, renamed)) {
+      return Err(call_site_error(format!(
+        "Class names `{}` and `{}` both become the field `{}`",
+        other, name, field
+      )));
+    }
+  }
+  let idents = fields
+    .values()
+    .map(|(field, _, _)| field)
+    .collect::<Vec<_>>();
+  let values = fields.values().map(|(_, _, renamed)| renamed);
+  let expanded = quote! {
+    {
+      #[allow(non_snake_case)]
+      struct CssModule {
+        #(pub #idents: &'static str,)*
+      }
+      CssModule {
+        #(#idents: #values,)*
+      }
+    }
+  };
+  Ok(match (inline_css, css_file) {
+    (Some(token_stream), _) => with_css_warnings(token_stream, expanded),
+    (None, Some((path, css, span))) => with_css_file_warnings(&path, &css, span, expanded),
+    (None, None) => expanded,
+  })
+}
+
+// class names can't be used as they are for the fields, e.g. `icon-left` becomes `icon_left` and `2xl` becomes `_2xl`.
+fn field_name(class: &str) -> String {
+  let field: String = class
+    .chars()
+    .map(|c| {
+      if c.is_alphanumeric() || c == '_' {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect();
+  if field.starts_with(|c: char| c.is_ascii_digit()) || field == "_" {
+    format!("_{}", field)
+  } else {
+    field
+  }
+}
+
+// keywords are used as raw identifiers, e.g. `r#type`, except the ones which can't be raw.
+fn field_ident(field: &str) -> Ident {
+  const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+  ];
+  match field {
+    "crate" | "self" | "Self" | "super" => Ident::new(&format!("{}_", field), Span::call_site()),
+    field if KEYWORDS.contains(&field) => Ident::new_raw(field, Span::call_site()),
+    field => Ident::new(field, Span::call_site()),
+  }
 }
 
 #[proc_macro]
 pub fn style_sheet_str(token_stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
   let token_stream = TokenStream::from(token_stream).into_iter();
-  let expanded =
-    style_sheet_str_(token_stream).unwrap_or_else(|err| quote! { compile_error!(#err) });
+  let expanded = style_sheet_str_(token_stream).unwrap_or_else(|err| err);
   proc_macro::TokenStream::from(expanded)
 }
 
-fn style_sheet_str_(token_stream: impl Iterator<Item = TokenTree>) -> Result<TokenStream, String> {
+fn style_sheet_str_(
+  token_stream: impl Iterator<Item = TokenTree>,
+) -> Result<TokenStream, TokenStream> {
   let tokens = &token_stream.collect::<Vec<_>>();
   let &[TokenTree::Literal(path_literal)] = &tokens.as_slice() else {
-    return Err("Expected only a string literal".to_string());
+    return Err(call_site_error("Expected only a string literal"));
   };
 
   let path = StringLit::try_from(path_literal)
-    .map_err(|err| format!("Expected a string literal: {}", err))?;
-  let path = Path::new(path.value());
+    .map_err(|err| call_site_error(format!("Expected a string literal: {}", err)))?;
+  let path = path.value();
 
-  let style_sheet_content = fs::read_to_string(path).map_err(|_| "Expected to read file")?;
+  let style_sheet_content = fs::read_to_string(path)
+    .map_err(|err| call_site_error(format!("Expected to read file {}: {}", path, err)))?;
+  let file_errors = |diagnostics| css_file_errors(path, diagnostics, path_literal.span());
 
-  let class = Class::random();
-  let style = from_str(&style_sheet_content, &class);
+  let (naming, scope_options) = config()?;
+  let class = Class::from_css(
+    &style_sheet_content,
+    &naming,
+    &scope_options,
+    path_hint(Path::new(path)),
+  )
+  .map_err(file_errors)?;
+  let style = from_str(&style_sheet_content, &class, &scope_options).map_err(file_errors)?;
+  let style = css_literal(&style)?;
 
-  Ok(quote! { (#class, #style) })
+  Ok(with_css_file_warnings(
+    path,
+    &style_sheet_content,
+    path_literal.span(),
+    quote! { (#class, #style) },
+  ))
 }
 
 #[doc(hidden)]
 #[proc_macro]
 pub fn style_sheet_test(token_stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
   let token_stream = TokenStream::from(token_stream).into_iter();
-  let expanded =
-    style_sheet_test_(token_stream).unwrap_or_else(|err| quote! { compile_error!(#err) });
+  let expanded = style_sheet_test_(token_stream).unwrap_or_else(|err| err);
   proc_macro::TokenStream::from(expanded)
 }
 
-fn style_sheet_test_(token_stream: impl Iterator<Item = TokenTree>) -> Result<TokenStream, String> {
+fn style_sheet_test_(
+  token_stream: impl Iterator<Item = TokenTree>,
+) -> Result<TokenStream, TokenStream> {
   let tokens = &token_stream.collect::<Vec<_>>();
   let &[TokenTree::Literal(path_literal)] = &tokens.as_slice() else {
-    return Err("Expected only a string literal".to_string());
+    return Err(call_site_error("Expected only a string literal"));
   };
 
   let path = StringLit::try_from(path_literal)
-    .map_err(|err| format!("Expected a string literal: {}", err))?;
-  let path = Path::new(path.value());
+    .map_err(|err| call_site_error(format!("Expected a string literal: {}", err)))?;
+  let path = path.value();
 
-  let style_sheet_content = fs::read_to_string(path).map_err(|_| "Expected to read file")?;
+  let style_sheet_content = fs::read_to_string(path)
+    .map_err(|err| call_site_error(format!("Expected to read file {}: {}", path, err)))?;
+  let file_errors = |diagnostics| css_file_errors(path, diagnostics, path_literal.span());
 
   let class = Class::new("test".into());
-  let style = from_str(&style_sheet_content, &class);
+  let style =
+    from_str(&style_sheet_content, &class, &ScopeOptions::default()).map_err(file_errors)?;
 
   Ok(quote! { #style })
 }
@@ -111,15 +366,113 @@ fn style_sheet_test_(token_stream: impl Iterator<Item = TokenTree>) -> Result<To
 #[proc_macro]
 pub fn style_test(token_stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
   let token_stream = TokenStream::from(token_stream).into_iter();
-  let expanded = style_test_(token_stream).unwrap_or_else(|err| quote! { compile_error!(#err) });
+  let expanded = style_test_(token_stream).unwrap_or_else(|err| err);
   proc_macro::TokenStream::from(expanded)
 }
 
-fn style_test_(token_stream: impl Iterator<Item = TokenTree>) -> Result<TokenStream, String> {
+fn style_test_(token_stream: impl Iterator<Item = TokenTree>) -> Result<TokenStream, TokenStream> {
   let token_stream = token_stream.into_iter();
 
   let class = Class::new("test".into());
-  let (style, _selectors) = from_ts(token_stream, &class, true);
+  let (style, _selectors) =
+    from_ts(token_stream, &class, &ScopeOptions::default(), true).map_err(css_errors)?;
 
   Ok(quote! { #style })
 }
+
+/// Css returned by the `*_str!` macros, prefixed for the [`targets`].
+/// With a configured [`Indent`] it is pretty printed in debug builds of the calling crate.
+fn css_literal(style: &str) -> Result<TokenStream, TokenStream> {
+  let style = prefix_css(style, &targets()?).map_err(generated_css_errors)?;
+  match Indent::from_cargo_env().map_err(call_site_error)? {
+    Some(indent) => {
+      let pretty = pretty_css(&style, indent).map_err(generated_css_errors)?;
+      Ok(quote! { if cfg!(debug_assertions) { #pretty } else { #style } })
+    }
+    None => Ok(quote! { #style }),
+  }
+}
+
+/// Class naming and scoping configuration of the crate being compiled.
+fn config() -> Result<(ClassNaming, ScopeOptions), TokenStream> {
+  track_config();
+  let naming = ClassNaming::from_cargo_env().map_err(call_site_error)?;
+  let scope_options = ScopeOptions::from_cargo_env().map_err(call_site_error)?;
+  Ok((naming, scope_options))
+}
+
+// the configuration is read by stylers_core with `std::env` and `std::fs`, which the compiler doesn't see.
+// Tracking it reruns the macros when it changes, so that their classes stay the same as the ones of `stylers::build`.
+fn track_config() {
+  for var in CONFIG_ENV_VARS {
+    let _ = proc_macro::tracked::env_var(var);
+  }
+  if let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
+    proc_macro::tracked::path(Path::new(&manifest_dir).join("Cargo.toml"));
+  }
+}
+
+/// Browsers the css returned by the `*_str!` macros is prefixed for, see [`Targets`].
+fn targets() -> Result<Targets, TokenStream> {
+  Targets::from_cargo_env().map_err(call_site_error)
+}
+
+fn call_site_error(message: impl ToString) -> TokenStream {
+  let message = message.to_string();
+  quote! { compile_error!(#message) }
+}
+
+// css built by the macro which doesn't parse again is reported rather than emitted half processed.
+fn generated_css_errors(diagnostics: Vec<Diagnostic>) -> TokenStream {
+  let mut errors = diagnostics
+    .iter()
+    .map(|diagnostic| call_site_error(format!("Generated css:{}", diagnostic)));
+  let last = errors.next_back();
+  quote! { { #(#errors;)* #last } }
+}
+
+// every css error is reported at its own token.
+// the last one is the value of the block so that the macro call doesn't cause type errors on top of them.
+fn css_errors(errors: Vec<stylers_core::Error>) -> TokenStream {
+  let mut errors = errors.iter().map(stylers_core::Error::to_compile_error);
+  let last = errors.next_back();
+  quote! { { #(#errors;)* #last } }
+}
+
+// warnings of valid css are statements of a block around the expanded macro, each reported at its own token.
+fn with_css_warnings(token_stream: TokenStream, expanded: TokenStream) -> TokenStream {
+  let warnings = warnings_from_ts(token_stream.into_iter(), true);
+  if warnings.is_empty() {
+    return expanded;
+  }
+  let warnings = warnings.iter().map(stylers_core::Error::to_compile_warning);
+  quote! { { #(#warnings)* #expanded } }
+}
+
+// warnings of a css file are all reported at the path literal, located by their line and column in the file.
+fn with_css_file_warnings(path: &str, css: &str, span: Span, expanded: TokenStream) -> TokenStream {
+  let warnings = warnings_from_str(css);
+  if warnings.is_empty() {
+    return expanded;
+  }
+  let warnings = warnings.iter().map(|diagnostic| {
+    stylers_core::Error::new(format!("{}:{}", path, diagnostic), span).to_compile_warning()
+  });
+  quote! { { #(#warnings)* #expanded } }
+}
+
+// errors of a css file are reported at the tokens naming the file, with their location inside of the file.
+fn css_file_errors(path: &str, diagnostics: Vec<Diagnostic>, span: Span) -> TokenStream {
+  let mut errors = diagnostics.iter().map(|diagnostic| {
+    let message = format!("{}:{}", path, diagnostic);
+    quote_spanned! { span => ::core::compile_error!(#message) }
+  });
+  let last = errors.next_back();
+  quote! { { #(#errors;)* #last } }
+}
+
+/// Human readable hint for the class of an inline style, `stylers::build` uses the same [`path_hint`].
+fn source_hint() -> Option<String> {
+  let file = proc_macro::Span::call_site().file();
+  path_hint(Path::new(&file)).map(str::to_string)
+}
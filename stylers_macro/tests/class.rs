@@ -0,0 +1,91 @@
+// `stylers::build` derives classes from the parsed source file, so these tests check that
+// the macros and a plain token stream / file read agree on the class name.
+use proc_macro2::TokenStream;
+use stylers_core::{Class, ClassNaming, ScopeOptions};
+use stylers_macro::{style, style_sheet, style_sheet_str, style_str};
+
+#[test]
+fn style_matches_build() {
+  let class = style! {
+      .two .one{
+          color: yellow;
+      }
+  };
+  let ts: TokenStream = ".two .one{ color: yellow; }".parse().unwrap();
+  assert_eq!(
+    class,
+    Class::from_ts(
+      ts.into_iter(),
+      false,
+      &ClassNaming::default(),
+      &ScopeOptions::default(),
+      None
+    )
+    .unwrap()
+    .as_name()
+  );
+}
+
+#[test]
+fn style_same_length_distinct_class() {
+  let one = style! { button { color: red; } };
+  let two = style! { button { color: tan; } };
+  assert_ne!(one, two);
+}
+
+#[test]
+fn style_sheet_matches_build() {
+  let class = style_sheet!("stylers_macro/tests/samples/basics.css");
+  let css = std::fs::read_to_string("tests/samples/basics.css").unwrap();
+  assert_eq!(
+    class,
+    Class::from_css(
+      &css,
+      &ClassNaming::default(),
+      &ScopeOptions::default(),
+      None
+    )
+    .unwrap()
+    .as_name()
+  );
+}
+
+#[test]
+fn style_str_is_reproducible() {
+  let (class, style) = style_str! {
+      .two .one{
+          color: yellow;
+      }
+  };
+  let ts: TokenStream = ".two .one{ color: yellow; }".parse().unwrap();
+  let expected = Class::from_ts(
+    ts.into_iter(),
+    false,
+    &ClassNaming::default(),
+    &ScopeOptions::default(),
+    None,
+  )
+  .unwrap();
+  assert_eq!(class, expected.as_name());
+  assert_eq!(
+    style,
+    format!(".two{0} .one{0}{{color: yellow;}}", expected.as_selector())
+  );
+}
+
+#[test]
+fn style_sheet_str_is_reproducible() {
+  let (class, _style) = style_sheet_str!("stylers_macro/tests/samples/basics.css");
+  let css = std::fs::read_to_string("tests/samples/basics.css").unwrap();
+  assert_eq!(
+    class,
+    Class::from_css(
+      &css,
+      &ClassNaming::default(),
+      &ScopeOptions::default(),
+      None
+    )
+    .unwrap()
+    .as_name()
+  );
+}
@@ -277,7 +277,7 @@ fn test_26() {
       svg|a {
       }
   };
-  assert_eq!(style, "svg.test|a.test{}");
+  assert_eq!(style, "svg|a.test{}");
 
   //Regular at-rules
 }
@@ -363,7 +363,7 @@ fn test_32() {
   };
   assert_eq!(
     style,
-    r#"@page{size: A4;margin: 10%;@top-left-corner {content: "Page " counter(page);}}"#
+    r#"@page{size: A4;margin: 10%;@top-left-corner{content: "Page " counter(page);}}"#
   );
 }
 
@@ -379,7 +379,7 @@ fn test_33() {
   };
   assert_eq!(
     style,
-    r#"@font-face{font-family: "Trickster";src: local("Trickster"),url("trickster-COLRv1.otf") format("opentype") tech(color-COLRv1), url("trickster-outline.otf")format("opentype"), url("trickster-outline.woff") format("woff");}"#
+    r#"@font-face{font-family: "Trickster";src: local("Trickster"), url("trickster-COLRv1.otf") format("opentype") tech(color-COLRv1), url("trickster-outline.otf") format("opentype"), url("trickster-outline.woff") format("woff");}"#
   );
 
   // todo: currently we not adding any random string to keyframe identifier.
@@ -397,7 +397,7 @@ fn test_34() {
   };
   assert_eq!(
     style,
-    "@keyframes spin1{to {-webkit-transform: rotate(360deg);}}"
+    "@keyframes spin1-test{to{-webkit-transform: rotate(360deg);}}"
   );
 }
 
@@ -412,7 +412,7 @@ fn test_35() {
   };
   assert_eq!(
     style,
-    "@-webkit-keyframes spin2{to {-webkit-transform: rotate(360deg);}}"
+    "@-webkit-keyframes spin2-test{to{-webkit-transform: rotate(360deg);}}"
   );
 
   //note: here we have to declare raw string because of backslash charactor
@@ -444,7 +444,7 @@ fn test_37() {
   };
   assert_eq!(
     style,
-    r#"@font-feature-values Font One{@styleset {nice-style: 12;}}"#
+    r#"@font-feature-values Font One{@styleset{nice-style: 12;}}"#
   );
 
   //note: this is experimental css rule.
@@ -516,7 +516,7 @@ fn test_42() {
   };
   assert_eq!(
     style,
-    ":root{--blue: #1e90ff;}body.test{background-color: var(--blue);}"
+    ":root{--blue: #1e90ff;}body{background-color: var(--blue);}"
   );
 }
 
@@ -672,3 +672,246 @@ fn test_54() {
     ".errorSign.test{transform-box: fill-box;transform-origin: center;scrollbar-width: 1px;scrollbar-color: red;}"
   );
 }
+
+#[test]
+fn test_55() {
+  let style = style_test! {
+      .card {
+          color: red;
+          &:hover {
+              color: blue;
+          }
+          & > .title,
+          .body {
+              margin: 0;
+          }
+          .icon & {
+              display: none;
+          }
+          @media (min-width: 600px) {
+              padding: 1rem;
+              &.wide {
+                  width: 100%;
+              }
+          }
+      }
+      ul, ol {
+          li { list-style: none; }
+      }
+  };
+  assert_eq!(
+    style,
+    ".card.test{color: red;}.card.test:hover{color: blue;}.card.test>.title.test,.card.test .body.test{margin: 0;}.icon.test .card.test{display: none;}@media (min-width: 600px){.card.test{padding: 1rem;}.card.wide.test{width: 100%;}}ul.test li.test,ol.test li.test{list-style: none;}"
+  );
+}
+
+#[test]
+fn test_56() {
+  let style = style_test! {
+      @scoping root;
+      div .one > p, :deep(h3) span {
+          color: red;
+      }
+      .card {
+          & .title { margin: 0; }
+      }
+  };
+  assert_eq!(
+    style,
+    "div.test .one>p,h3 span.test{color: red;}.card.test .title{margin: 0;}"
+  );
+}
+
+#[test]
+fn test_57() {
+  let style = style_test! {
+      @scoping last;
+      div .one > p::before, div :deep(h3) {
+          color: red;
+      }
+      @media (min-width: 600px) {
+          ul li { margin: 0; }
+      }
+  };
+  assert_eq!(
+    style,
+    "div .one>p.test::before,div.test h3{color: red;}@media (min-width: 600px){ul li.test{margin: 0;}}"
+  );
+}
+
+#[test]
+fn test_58() {
+  let style = style_test! {
+      :global(body) .app, .btn:global(.active):hover {
+          color: red;
+      }
+      .modal :global(.portal > div) p {
+          margin: 0;
+      }
+      :global .toast span {
+          top: 0;
+      }
+      :global {
+          html { font-size: 14px; }
+          .dark & { color: white; }
+      }
+  };
+  assert_eq!(
+    style,
+    "body .app.test,.btn.test.active:hover{color: red;}.modal.test .portal>div p.test{margin: 0;}.toast span{top: 0;}html{font-size: 14px;}.dark{color: white;}"
+  );
+}
+
+#[test]
+fn test_59() {
+  let style = style_test! {
+      .a:not(.b), :is(h1, h2) span {
+          color: red;
+      }
+      .card:has(> img) p::first-line {
+          margin: 0;
+      }
+      li:where(.x .y, :not(.z))::before {
+          color: blue;
+      }
+  };
+  assert_eq!(
+    style,
+    ".a.test:not(.b.test),:is(h1.test,h2.test) span.test{color: red;}.card.test:has(>img.test) p.test::first-line{margin: 0;}li:where(.x.test .y.test,.test:not(.z.test))::before{color: blue;}"
+  );
+}
+
+#[test]
+fn test_60() {
+  let style = style_test! {
+      a[title="x]y"], [ data-tip = "a:b, c" ]:hover, [lang=en i] p {
+          color: red;
+      }
+      [xlink|href^="#"], input[type="text" s] {
+          margin: 0;
+      }
+  };
+  assert_eq!(
+    style,
+    r##"a[title="x]y"].test,[data-tip="a:b, c"].test:hover,[lang=en i].test p.test{color: red;}[xlink|href^="#"].test,input[type="text" s].test{margin: 0;}"##
+  );
+}
+
+#[test]
+fn test_61() {
+  let style = style_test! {
+      :root.dark .card, :root, .theme {
+          color: white;
+      }
+      html[lang="en"] body > main p, :host(.active) span, :host {
+          margin: 0;
+      }
+  };
+  assert_eq!(
+    style,
+    r#":root.dark .card.test,:root,.theme.test{color: white;}html[lang="en"] body>main.test p.test,:host(.active) span.test,:host{margin: 0;}"#
+  );
+}
+
+#[test]
+fn test_62() {
+  let style = style_test! {
+      @keyframes spin {
+          to { transform: rotate(360deg); }
+      }
+      @keyframes :global(fade) {
+          from { opacity: 0; }
+      }
+      .loader {
+          animation: spin 1s linear infinite, fade 2s;
+      }
+      @media (prefers-reduced-motion: no-preference) {
+          .icon { -webkit-animation-name: spin; }
+      }
+  };
+  assert_eq!(
+    style,
+    "@keyframes spin-test{to{transform: rotate(360deg);}}@keyframes fade{from{opacity: 0;}}.loader.test{animation: spin-test 1s linear infinite, fade 2s;}@media (prefers-reduced-motion: no-preference){.icon.test{-webkit-animation-name: spin-test;}}"
+  );
+}
+
+#[test]
+fn test_63() {
+  let style = style_test! {
+      .a {
+          div& { color: red; }
+          *&:hover { color: blue; }
+      }
+      p {
+          div& { margin: 0; }
+      }
+      .a, .b {
+          & + & { margin: 0; }
+      }
+  };
+  assert_eq!(
+    style,
+    "div.a.test{color: red;}.test.a:hover{color: blue;}div:is(p.test){margin: 0;}.a.test+.a.test,.a.test+.b.test,.b.test+.a.test,.b.test+.b.test{margin: 0;}"
+  );
+}
+
+#[test]
+fn test_64() {
+  let style = style_test! {
+      li:nth-child(2n + 1 of .item, .entry) {
+          color: red;
+      }
+      li:nth-last-child(odd of .item), li:nth-child(2) {
+          margin: 0;
+      }
+  };
+  assert_eq!(
+    style,
+    "li.test:nth-child(2n + 1 of .item.test,.entry.test){color: red;}li.test:nth-last-child(odd of .item.test),li.test:nth-child(2){margin: 0;}"
+  );
+}
+
+#[test]
+fn test_65() {
+  let style = style_test! {
+      .a > .b {
+          & + & { margin: 0; }
+          .c &.d { color: red; }
+          & p { top: 0; }
+      }
+  };
+  assert_eq!(
+    style,
+    ".a.test>.b.test+:is(.a.test>.b.test){margin: 0;}.c.test :is(.a.test>.b.test).d{color: red;}.a.test>.b.test p.test{top: 0;}"
+  );
+}
+
+#[test]
+fn test_66() {
+  let style = style_test! {
+      @scoping root;
+      :is(.a .b, h1) span { color: red; }
+      .card:not(.x .y) p { margin: 0; }
+      .list :where(.item) { top: 0; }
+      .card:has(> img) { left: 0; }
+  };
+  assert_eq!(
+    style,
+    ":is(.a .b.test,h1.test) span{color: red;}.card.test:not(.x .y.test) p{margin: 0;}.list.test :where(.item){top: 0;}.card.test:has(> img){left: 0;}"
+  );
+}
+
+#[test]
+fn test_67() {
+  let style = style_test! {
+      @scoping last;
+      :is(.a .b, h1) span { color: red; }
+      .card:not(.x .y) p { margin: 0; }
+      .list :where(.item) { top: 0; }
+      .card:has(> img) { left: 0; }
+  };
+  assert_eq!(
+    style,
+    ":is(.a .b, h1) span.test{color: red;}.card:not(.x .y) p.test{margin: 0;}.list :where(.item.test){top: 0;}.card.test:has(> img){left: 0;}"
+  );
+}
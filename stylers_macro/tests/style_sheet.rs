@@ -1,4 +1,4 @@
-use stylers_macro::style_sheet_test;
+use stylers_macro::{style_sheet_test, style_test};
 
 // note: to check why test cases are failing we can use the text compare tools to compare and see the differences between left and right values of a test case
 // note: temporarily writing these tests. once find a way to test styler_core module we can discard this.
@@ -30,7 +30,7 @@ fn pseudo() {
 
   assert_eq!(
     style,
-    r#".one.test:hover{background-color: green;}p.test::before{content: "Read this: ";}div.test:nth-child(2){background-color: green;}p.test:lang(it){background: yellow;}svg.test|a.test{}.test:not(body){background: #ff0000;}:root{--blue: #1e90ff;}body.test{background-color: var(--blue);}#container.test{--first-color: #290;}#thirdParagraph.test{background-color: var(--first-color);color: var(--second-color);}"#
+    r#".one.test:hover{background-color: green;}p.test::before{content: "Read this: ";}div.test:nth-child(2){background-color: green;}p.test:lang(it){background: yellow;}svg|a.test{}.test:not(body){background: #ff0000;}:root{--blue: #1e90ff;}body{background-color: var(--blue);}#container.test{--first-color: #290;}#thirdParagraph.test{background-color: var(--first-color);color: var(--second-color);}"#
   );
 }
 
@@ -39,7 +39,7 @@ fn at_rules() {
   let style = style_sheet_test!("stylers_macro/tests/samples/at_rules.css");
   assert_eq!(
     style,
-    r#"@charset "UTF-8";@import url("landscape.css") screen and (orientation: landscape);@namespace svg url("http://www.w3.org/2000/svg");@layer theme,layout,utilities;@supports (display: flex) {@media screen and (min-width: 900px) {article.test{display: flex;}}}@supports (display: flex) {.flex-container.test>.test{text-shadow: 0 0 2px blue;float: none;}.flex-container.test{display: flex;}}@document url("https://www.example.com/") {h1.test{color: green;}}@layer framework {@layer layout {p.test{margin-block: 1rem;font: 0.9em/1.2 Arial, Helvetica, sans-serif;content: "\hello";content: "\hello";}}}"#
+    r#"@charset "UTF-8";@import url("landscape.css") screen and (orientation: landscape);@namespace svg url("http://www.w3.org/2000/svg");@layer theme, layout, utilities;@supports (display: flex){@media screen and (min-width: 900px){article.test{display: flex;}}}@supports (display: flex){.flex-container.test>.test{text-shadow: 0 0 2px blue;float: none;}.flex-container.test{display: flex;}}@document url("https://www.example.com/"){h1.test{color: green;}}@layer framework{@layer layout{p.test{margin-block: 1rem;font: 0.9em/1.2 Arial, Helvetica, sans-serif;content: "\hello";content: "\hello";}}}"#
   );
 }
 
@@ -49,7 +49,7 @@ fn special_at_rules() {
 
   assert_eq!(
     style,
-    r#"@page {size: A4;margin: 10%;@top-left-corner {content: "Page " counter(page);}}@font-face {font-family: "Trickster";src: local("Trickster"),url("trickster-COLRv1.otf") format("opentype") tech(color-COLRv1), url("trickster-outline.otf") format("opentype"), url("trickster-outline.woff") format("woff");}@keyframes spin1 {to {-webkit-transform: rotate(360deg);}}@-webkit-keyframes spin2 {to {-webkit-transform: rotate(360deg);}}@counter-style thumbs {system: cyclic;symbols: "\1F44D";suffix: " ";}@font-feature-values Font One {@styleset {nice-style: 12;}}@property --property-name {syntax: "<color>";inherits: false;initial-value: #c0ffee;}"#
+    r#"@page{size: A4;margin: 10%;@top-left-corner{content: "Page " counter(page);}}@font-face{font-family: "Trickster";src: local("Trickster"), url("trickster-COLRv1.otf") format("opentype") tech(color-COLRv1), url("trickster-outline.otf") format("opentype"), url("trickster-outline.woff") format("woff");}@keyframes spin1-test{to{-webkit-transform: rotate(360deg);}}@-webkit-keyframes spin2-test{to{-webkit-transform: rotate(360deg);}}@counter-style thumbs{system: cyclic;symbols: "\1F44D";suffix: " ";}@font-feature-values Font One{@styleset{nice-style: 12;}}@property --property-name{syntax: "<color>";inherits: false;initial-value: #c0ffee;}"#
   );
 }
 
@@ -62,3 +62,88 @@ fn custom_pseudo_class() {
     r#"h3 div{color: orange;}div.test h3{color: orange;}div.test>h3{color: orange;}"#
   );
 }
+
+#[test]
+fn same_css_as_style_macro() {
+  let from_file = style_sheet_test!("stylers_macro/tests/samples/shared.css");
+  let from_tokens = style_test! {
+    /* the same css is written inside of style_test! in style_sheet.rs */
+    @media screen and (min-width: 900px) {
+      div > p.one,
+      h2 {
+        margin: 0
+          auto;
+        font: 12px/1.2 Arial, sans-serif !important;
+      }
+    }
+    .quote::after {
+      content: raw_str(r"\201D");
+    }
+    @font-face {
+      font-family: "Trickster";
+      src: local("Trickster"),
+        url("trickster.woff") format("woff");
+    }
+    @keyframes spin {
+      from { transform: rotate(0deg) }
+      50% { transform: rotate(180deg); }
+    }
+    a[title="x, y"]:hover { color: #1e90ff }
+  };
+
+  assert_eq!(from_file, from_tokens);
+  assert_eq!(
+    from_file,
+    r#"@media screen and (min-width: 900px){div.test>p.one.test,h2.test{margin: 0 auto;font: 12px/1.2 Arial, sans-serif !important;}}.quote.test::after{content: "\201D";}@font-face{font-family: "Trickster";src: local("Trickster"), url("trickster.woff") format("woff");}@keyframes spin-test{from{transform: rotate(0deg);}50%{transform: rotate(180deg);}}a[title="x, y"].test:hover{color: #1e90ff;}"#
+  );
+}
+
+#[test]
+fn nesting() {
+  let style = style_sheet_test!("stylers_macro/tests/samples/nesting.css");
+
+  assert_eq!(
+    style,
+    ".card.test{color: red;}.card.test:hover{color: blue;}.card.test>.title.test,.card.test .body.test{margin: 0;}.icon.test .card.test{display: none;}@media (min-width: 600px){.card.test{padding: 1rem;}.card.wide.test{width: 100%;}}ul.test li.test,ol.test li.test{list-style: none;}"
+  );
+}
+
+#[test]
+fn scoping() {
+  let style = style_sheet_test!("stylers_macro/tests/samples/scoping.css");
+
+  assert_eq!(
+    style,
+    ".list.test li a{color: red;}.list.test>.third-party span{color: blue;}:root.dark .list.test a{color: white;}"
+  );
+}
+
+#[test]
+fn global() {
+  let style = style_sheet_test!("stylers_macro/tests/samples/global.css");
+
+  assert_eq!(
+    style,
+    "body .app.test,.btn.test.active:hover{color: red;}.modal.test .portal>div p.test{margin: 0;}.toast span{top: 0;}html{font-size: 14px;}.dark{color: white;}"
+  );
+}
+
+#[test]
+fn attributes() {
+  let style = style_sheet_test!("stylers_macro/tests/samples/attributes.css");
+
+  assert_eq!(
+    style,
+    r##"a[title="x]y"].test,[data-tip='a:b, c'].test:hover,[lang=en i].test p.test{color: red;}[xlink|href^="#"].test,[*|lang].test,[|id].test{margin: 0;}a[title="say \"hi]\""].test,.a\:b[data-x=\31 0].test{top: 0;}"##
+  );
+}
+
+#[test]
+fn keyframes() {
+  let style = style_sheet_test!("stylers_macro/tests/samples/keyframes.css");
+
+  assert_eq!(
+    style,
+    "@keyframes spin-test{to{transform: rotate(360deg);}}@keyframes fade{from{opacity: 0;}}.loader.test{animation: spin-test 1s linear infinite, fade 2s;}.icon.test{animation-name: fade, spin-test;}"
+  );
+}
@@ -0,0 +1,234 @@
+// Regenerates stylers_core/data/properties.toml from @webref/css, @mdn/data and @mdn/browser-compat-data,
+// and stylers_core/data/compat.toml, the browser versions needing prefixed variants, from @mdn/browser-compat-data.
+// Usage: `npm install && node generate.mjs` inside of tools/css-data.
+// The vendored files are currently maintained by hand following the same rules,
+// its output is a starting point to review against them rather than a drop-in replacement.
+//
+// Every property of the css specs known to webref or documented by MDN is listed,
+// with whether it is inherited and a shorthand, and its MDN status.
+// Vendor prefixed properties are only listed when MDN documents them on their own, e.g. `-webkit-line-clamp`,
+// the prefixes which exist for the standard properties come from the browser compat data.
+//
+// Left out on purpose:
+// - legacy aliases of webref like `-webkit-align-content`. They are the prefixed variants of the property they alias,
+//   so they are added to its prefixes instead. Listed as properties of their own they would be valid without the
+//   standard property, and stylers couldn't warn about `-webkit-transform` used without `transform` anymore.
+// - the properties of `excluded` below, each with the reason why.
+import { writeFile } from "node:fs/promises";
+import { fileURLToPath } from "node:url";
+import css from "@webref/css";
+import mdnProperties from "@mdn/data/css/properties.json" with { type: "json" };
+import bcd from "@mdn/browser-compat-data" with { type: "json" };
+
+const output = fileURLToPath(new URL("../../stylers_core/data/properties.toml", import.meta.url));
+const statuses = new Set(["standard", "experimental", "nonstandard", "obsolete"]);
+
+// properties of the specs or of MDN which stylers doesn't accept, with the reason why.
+const excluded = {
+  "box-reflect": "only ever shipped as `-webkit-box-reflect`, which is listed on its own",
+  "enable-background": "svg 1.1 property dropped by svg 2, no browser supports it",
+  "glyph-orientation-horizontal": "svg 1.1 property dropped by svg 2, no browser supports it",
+  kerning: "svg 1.1 property dropped by svg 2 in favor of `font-kerning`",
+};
+
+const { properties: webrefProperties, atrules } = await css.listAll();
+
+const properties = new Map();
+// vendor prefixes of the legacy aliases of a property, e.g. `-webkit-` of `-webkit-align-content` for `align-content`.
+const aliasPrefixes = new Map();
+for (const property of webrefProperties) {
+  if (property.legacyAliasOf) {
+    const prefix = property.name.slice(0, -property.legacyAliasOf.length);
+    aliasPrefixes.set(property.legacyAliasOf, new Set([...(aliasPrefixes.get(property.legacyAliasOf) ?? []), prefix]));
+    continue;
+  }
+  properties.set(property.name, {
+    // webref says "yes" or "no".
+    inherited: property.inherited === "yes",
+    // specs give the longhands instead of an initial value for shorthands.
+    shorthand: property.initial === "see individual properties",
+    status: "standard",
+  });
+}
+for (const [name, mdn] of Object.entries(mdnProperties)) {
+  const property = properties.get(name) ?? { inherited: false, shorthand: false, status: "standard" };
+  properties.set(name, {
+    inherited: mdn.inherited ?? property.inherited,
+    // MDN lists the longhands as the initial value of shorthands.
+    shorthand: Array.isArray(mdn.initial),
+    status: statuses.has(mdn.status) ? mdn.status : property.status,
+  });
+}
+for (const name of Object.keys(excluded)) {
+  properties.delete(name);
+}
+
+const descriptors = new Map();
+for (const atrule of atrules) {
+  const names = atrule.descriptors.map((descriptor) => descriptor.name);
+  if (names.length > 0) {
+    descriptors.set(atrule.name, [...new Set(names)].sort());
+  }
+}
+
+// vendor prefixes any browser ever supported a property with, e.g. `-moz-` for `appearance`.
+const vendorPrefixes = ["-webkit-", "-moz-", "-ms-", "-o-"];
+const prefixes = new Map(
+  [...aliasPrefixes].filter(([name]) => properties.has(name)).map(([name, aliases]) => [name, new Set(aliases)]),
+);
+for (const [name, feature] of Object.entries(bcd.css.properties)) {
+  if (!properties.has(name)) {
+    continue;
+  }
+  for (const statements of Object.values(feature.__compat?.support ?? {})) {
+    for (const statement of [statements].flat()) {
+      const prefix =
+        statement.prefix ??
+        vendorPrefixes.find((prefix) => statement.alternative_name === `${prefix}${name}`);
+      if (vendorPrefixes.includes(prefix)) {
+        prefixes.set(name, new Set([...(prefixes.get(name) ?? []), prefix]));
+      }
+    }
+  }
+}
+
+const lines = [
+  "# Css properties and at-rule descriptors known to stylers, extracted from @webref/css, @mdn/data and @mdn/browser-compat-data.",
+  "# Generated by `node tools/css-data/generate.mjs`, edit the tool instead of this file.",
+  "",
+  "properties = [",
+];
+for (const name of [...properties.keys()].sort()) {
+  const { inherited, shorthand, status } = properties.get(name);
+  lines.push(
+    `  { name = "${name}", inherited = ${inherited}, shorthand = ${shorthand}, status = "${status}" },`,
+  );
+}
+lines.push("]", "", "[descriptors]");
+for (const name of [...descriptors.keys()].sort()) {
+  const names = descriptors.get(name).map((descriptor) => `"${descriptor}"`).join(", ");
+  lines.push(`"${name}" = [${names}]`);
+}
+
+lines.push("", "[prefixes]");
+for (const name of [...prefixes.keys()].sort()) {
+  const names = [...prefixes.get(name)].sort().map((prefix) => `"${prefix}"`).join(", ");
+  lines.push(`${name} = [${names}]`);
+}
+
+await writeFile(output, lines.join("\n") + "\n");
+console.log(`Wrote ${properties.size} properties to ${output}`);
+
+// browserslist names of the browsers known to the targets, with their name in the compat data.
+const browsers = {
+  chrome: "chrome",
+  edge: "edge",
+  firefox: "firefox",
+  safari: "safari",
+  ios_saf: "safari_ios",
+  opera: "opera",
+  samsung: "samsunginternet_android",
+  and_chr: "chrome_android",
+  ie: "ie",
+};
+// browsers which browserslist considers dead, up to their last dead version.
+const deadUntil = { edge: "18", ie: "11" };
+// only the latest release of mobile browsers is known, as in browserslist.
+const latestOnly = new Set(["and_chr"]);
+// standard selectors with their prefixed variants, which aren't all listed as alternative names.
+const selectors = {
+  "::placeholder": ["::-webkit-input-placeholder", "::-moz-placeholder", ":-ms-input-placeholder", "::-ms-input-placeholder"],
+  "::selection": ["::-moz-selection"],
+  "::file-selector-button": ["::-webkit-file-upload-button"],
+  "::backdrop": ["::-webkit-backdrop"],
+  ":fullscreen": [":-webkit-full-screen", ":-moz-full-screen", ":-ms-fullscreen"],
+  ":any-link": [":-webkit-any-link", ":-moz-any-link"],
+  ":read-only": [":-moz-read-only"],
+  ":read-write": [":-moz-read-write"],
+  ":autofill": [":-webkit-autofill"],
+};
+
+const compatOutput = fileURLToPath(new URL("../../stylers_core/data/compat.toml", import.meta.url));
+const version = (added) => (typeof added === "string" ? added.replace("≤", "") : undefined);
+const released = (name) =>
+  Object.entries(bcd.browsers[name].releases)
+    .filter(([, release]) => ["retired", "current", "esr"].includes(release.status))
+    .map(([version]) => version)
+    .sort((a, b) => a.localeCompare(b, undefined, { numeric: true }));
+
+// version from which each browser supports the feature without the variant, for the browsers which shipped the variant.
+function support(compat, isVariant) {
+  const versions = {};
+  for (const [browser, name] of Object.entries(browsers)) {
+    const statements = [compat?.support?.[name] ?? []].flat();
+    if (!statements.some((statement) => isVariant(statement) && version(statement.version_added))) {
+      continue;
+    }
+    const standard = statements.find(
+      (statement) =>
+        !statement.prefix && !statement.alternative_name && !statement.flags && version(statement.version_added),
+    );
+    versions[browser] = standard ? version(standard.version_added) : "never";
+  }
+  return versions;
+}
+const inline = (versions) =>
+  `{ ${Object.entries(versions)
+    .map(([browser, version]) => `${browser} = "${version}"`)
+    .join(", ")} }`;
+
+const compat = [
+  "# Browser releases and the versions which need the vendor prefixed variants of css features, extracted from @mdn/browser-compat-data.",
+  "# A version means older versions need the variant, `never` that no version supports the standard feature.",
+  "# Generated by `node tools/css-data/generate.mjs`, edit the tool instead of this file.",
+];
+for (const [browser, name] of Object.entries(browsers)) {
+  const versions = latestOnly.has(browser) ? released(name).slice(-1) : released(name);
+  compat.push("", `[browsers.${browser}]`, `versions = [${versions.map((v) => `"${v}"`).join(", ")}]`);
+  const esr = Object.entries(bcd.browsers[name].releases)
+    .filter(([, release]) => release.status === "esr")
+    .map(([version]) => `"${version}"`);
+  if (esr.length > 0) {
+    compat.push(`esr = [${esr.join(", ")}]`);
+  }
+  if (deadUntil[browser]) {
+    compat.push(`dead-until = "${deadUntil[browser]}"`);
+  }
+}
+compat.push("", "[properties]");
+for (const name of [...prefixes.keys()].sort()) {
+  for (const prefix of [...prefixes.get(name)].sort()) {
+    const versions = support(bcd.css.properties[name]?.__compat, (statement) =>
+      statement.prefix === prefix || statement.alternative_name === `${prefix}${name}`,
+    );
+    // legacy properties like `box-orient` only exist prefixed, there is nothing to add the prefix to.
+    if (Object.keys(versions).length > 0 && properties.get(name).status !== "nonstandard") {
+      compat.push(`${name}."${prefix}" = ${inline(versions)}`);
+    }
+  }
+}
+compat.push("", "[selectors]");
+for (const [selector, variants] of Object.entries(selectors).sort()) {
+  const feature = bcd.css.selectors[selector.replace(/^:+/, "")];
+  for (const variant of variants) {
+    const versions = support(
+      feature?.__compat,
+      (statement) =>
+        statement.alternative_name === variant ||
+        (!statement.alternative_name && statement.prefix && variant.includes(statement.prefix)),
+    );
+    if (Object.keys(versions).length > 0) {
+      compat.push(`"${selector}"."${variant}" = ${inline(versions)}`);
+    }
+  }
+}
+compat.push("", "[at-rules]");
+for (const prefix of vendorPrefixes.toSorted()) {
+  const versions = support(bcd.css["at-rules"].keyframes.__compat, (statement) => statement.prefix === prefix);
+  if (Object.keys(versions).length > 0) {
+    compat.push(`keyframes."${prefix}" = ${inline(versions)}`);
+  }
+}
+
+await writeFile(compatOutput, compat.join("\n") + "\n");
+console.log(`Wrote the browser compat data to ${compatOutput}`);